@@ -22,11 +22,24 @@ impl AeadAlgorithm {
             AeadAlgorithm::Aes128Gcm => 12,
         }
     }
+
+    pub fn tag_len(&self) -> usize {
+        match self {
+            AeadAlgorithm::Aes128Gcm => 16,
+        }
+    }
 }
 
 pub trait Aead {
     fn encrypt(&mut self, ptext: &[u8], ctext: &mut [u8]);
     fn decrypt(&mut self, ctext: &[u8], ptext: &mut [u8]);
+    /// Restarts the context with a new nonce, keeping the key schedule.
+    fn set_nonce(&mut self, nonce: &[u8]);
+    /// Encrypts a whole message under a fresh nonce, authenticating `aad`
+    /// as well, and writes the tag. Only valid on a context made with
+    /// [`AeadBuilder::for_encryption`], which implementations check in
+    /// debug builds.
+    fn seal(&mut self, nonce: &[u8], aad: &[u8], ptext: &[u8], ctext: &mut [u8], tag: &mut [u8]);
 }
 
 pub trait AeadBuilder {
@@ -35,40 +48,336 @@ pub trait AeadBuilder {
     fn for_decryption(&mut self, key: &[u8]) -> Box<dyn Aead>;
 }
 
+/// Benchmarks encryption and decryption of `len` bytes with a single key.
 pub fn bench_aead<B, M>(
     group: &mut criterion::BenchmarkGroup<M>,
     algorithm: AeadAlgorithm,
     mut builder: B,
-    count: usize,
+    len: usize,
+) where
+    B: AeadBuilder,
+    M: criterion::measurement::Measurement,
+{
+    use criterion::black_box;
+    use rand::prelude::*;
+
+    let mut rng = rand::thread_rng();
+
+    let mut key_bytes = vec![0u8; algorithm.key_len()];
+    rng.fill(key_bytes.as_mut_slice());
+
+    let mut nonce_bytes = vec![0u8; algorithm.nonce_len()];
+    rng.fill(nonce_bytes.as_mut_slice());
+
+    let mut pbuf = vec![0u8; len];
+    rng.fill(pbuf.as_mut_slice());
+
+    // Decryption is fed real ciphertext, produced by a separate context
+    // so that the benchmarked ones start from the same state.
+    let mut cbuf = vec![0u8; len];
+    builder
+        .nonce(&nonce_bytes)
+        .for_encryption(&key_bytes)
+        .encrypt(&pbuf, &mut cbuf);
+
+    let mut ctx = builder.nonce(&nonce_bytes).for_encryption(&key_bytes);
+    let mut out = vec![0u8; len];
+
+    group.bench_with_input(
+        criterion::BenchmarkId::new(format!("{}/encrypt", algorithm.name()), len),
+        &pbuf,
+        |b, param| {
+            b.iter(|| {
+                ctx.encrypt(black_box(param), black_box(&mut out));
+            });
+        },
+    );
+
+    let mut ctx = builder.nonce(&nonce_bytes).for_decryption(&key_bytes);
+
+    group.bench_with_input(
+        criterion::BenchmarkId::new(format!("{}/decrypt", algorithm.name()), len),
+        &cbuf,
+        |b, param| {
+            b.iter(|| {
+                ctx.decrypt(black_box(param), black_box(&mut out));
+            });
+        },
+    );
+}
+
+/// Registers the cases of [`bench_aead`] with a Callgrind runner, each
+/// encrypting or decrypting `len` bytes once. The inputs come from a
+/// fixed seed, so that runs are repeatable.
+pub fn callgrind_aead<B>(
+    runner: &mut crate::Callgrind,
+    algorithm: AeadAlgorithm,
+    mut builder: B,
+    len: usize,
+) where
+    B: AeadBuilder,
+{
+    use criterion::black_box;
+    use rand::prelude::*;
+
+    let mut rng = StdRng::seed_from_u64(0);
+
+    let mut key_bytes = vec![0u8; algorithm.key_len()];
+    rng.fill(key_bytes.as_mut_slice());
+
+    let mut nonce_bytes = vec![0u8; algorithm.nonce_len()];
+    rng.fill(nonce_bytes.as_mut_slice());
+
+    let mut pbuf = vec![0u8; len];
+    rng.fill(pbuf.as_mut_slice());
+
+    let mut cbuf = vec![0u8; len];
+    builder
+        .nonce(&nonce_bytes)
+        .for_encryption(&key_bytes)
+        .encrypt(&pbuf, &mut cbuf);
+
+    let mut ctx = builder.nonce(&nonce_bytes).for_encryption(&key_bytes);
+    let mut out = vec![0u8; len];
+    runner.case(format!("{}/encrypt/{}", algorithm.name(), len), move || {
+        ctx.encrypt(black_box(&pbuf), black_box(&mut out));
+    });
+
+    let mut ctx = builder.nonce(&nonce_bytes).for_decryption(&key_bytes);
+    let mut out = vec![0u8; len];
+    runner.case(format!("{}/decrypt/{}", algorithm.name(), len), move || {
+        ctx.decrypt(black_box(&cbuf), black_box(&mut out));
+    });
+}
+
+/// Benchmarks sealing independent messages of `len` bytes with a single
+/// key, setting a new nonce and finalising the tag for each one, as a TLS
+/// or QUIC record layer does. `aad_len` bytes of AAD are authenticated
+/// with each message.
+pub fn bench_aead_messages<B, M>(
+    group: &mut criterion::BenchmarkGroup<M>,
+    algorithm: AeadAlgorithm,
+    mut builder: B,
+    len: usize,
+    aad_len: usize,
 ) where
     B: AeadBuilder,
     M: criterion::measurement::Measurement,
 {
-    let len = crate::STEP * count;
+    use criterion::black_box;
+    use rand::prelude::*;
+
+    let mut rng = rand::thread_rng();
+
+    let mut key_bytes = vec![0u8; algorithm.key_len()];
+    rng.fill(key_bytes.as_mut_slice());
+
+    let mut nonce_bytes = vec![0u8; algorithm.nonce_len()];
+    rng.fill(nonce_bytes.as_mut_slice());
+
+    let mut aad_bytes = vec![0u8; aad_len];
+    rng.fill(aad_bytes.as_mut_slice());
+
+    let mut pbuf = vec![0u8; len];
+    rng.fill(pbuf.as_mut_slice());
+
+    let mut ctx = builder.nonce(&nonce_bytes).for_encryption(&key_bytes);
+
+    let mut cbuf = vec![0u8; len];
+    let mut tag = vec![0u8; algorithm.tag_len()];
 
     group.bench_with_input(
-        criterion::BenchmarkId::new(algorithm.name(), count),
-        &len,
+        criterion::BenchmarkId::new(format!("{}/seal/aad-{}", algorithm.name(), aad_len), len),
+        &pbuf,
         |b, param| {
-            use criterion::black_box;
-            use rand::prelude::*;
+            // Like a record layer, the nonce is derived from a sequence
+            // number.
+            let mut seq = 0u64;
+            b.iter(|| {
+                let nonce_len = nonce_bytes.len();
+                nonce_bytes[nonce_len - 8..].copy_from_slice(&seq.to_be_bytes());
+                seq = seq.wrapping_add(1);
+                ctx.seal(
+                    black_box(&nonce_bytes),
+                    black_box(&aad_bytes),
+                    black_box(param),
+                    black_box(&mut cbuf),
+                    black_box(&mut tag),
+                );
+            });
+        },
+    );
+}
+
+/// Benchmarks creating encryption and decryption contexts, which runs the
+/// key schedule, and restarting an existing context with a new nonce.
+/// Dropping the contexts is not timed.
+pub fn bench_aead_setup<B, M>(
+    group: &mut criterion::BenchmarkGroup<M>,
+    algorithm: AeadAlgorithm,
+    mut builder: B,
+) where
+    B: AeadBuilder,
+    M: criterion::measurement::Measurement,
+{
+    use criterion::black_box;
+    use rand::prelude::*;
+
+    let mut rng = rand::thread_rng();
+
+    let mut key_bytes = vec![0u8; algorithm.key_len()];
+    rng.fill(key_bytes.as_mut_slice());
+
+    let mut nonce_bytes = vec![0u8; algorithm.nonce_len()];
+    rng.fill(nonce_bytes.as_mut_slice());
+
+    group.bench_function(
+        criterion::BenchmarkId::new(algorithm.name(), "for-encryption"),
+        |b| {
+            b.iter_with_large_drop(|| {
+                builder
+                    .nonce(black_box(&nonce_bytes))
+                    .for_encryption(black_box(&key_bytes))
+            });
+        },
+    );
+
+    group.bench_function(
+        criterion::BenchmarkId::new(algorithm.name(), "for-decryption"),
+        |b| {
+            b.iter_with_large_drop(|| {
+                builder
+                    .nonce(black_box(&nonce_bytes))
+                    .for_decryption(black_box(&key_bytes))
+            });
+        },
+    );
+
+    let mut ctx = builder.nonce(&nonce_bytes).for_encryption(&key_bytes);
+
+    group.bench_function(
+        criterion::BenchmarkId::new(algorithm.name(), "set-nonce"),
+        |b| {
+            b.iter(|| ctx.set_nonce(black_box(&nonce_bytes)));
+        },
+    );
+}
+
+/// The rekey intervals, in messages, swept by the session churn workload.
+pub const REKEY_INTERVALS: [usize; 7] = [1, 10, 100, 1_000, 10_000, 100_000, 1_000_000];
+
+/// Benchmarks a stream of `len`-byte messages, each sealed under its own
+/// nonce, where a fresh context with the next key is created every
+/// `interval` messages. One iteration is one message, so the key setup
+/// cost is amortised over `interval` iterations.
+pub fn bench_aead_rekey<B, M>(
+    group: &mut criterion::BenchmarkGroup<M>,
+    algorithm: AeadAlgorithm,
+    mut builder: B,
+    len: usize,
+    interval: usize,
+) where
+    B: AeadBuilder,
+    M: criterion::measurement::Measurement,
+{
+    use criterion::black_box;
+    use rand::prelude::*;
 
-            let mut rng = rand::thread_rng();
+    let mut rng = rand::thread_rng();
 
+    // Keys are generated up front so that the random number generator is
+    // not timed.
+    let keys: Vec<Vec<u8>> = (0..16)
+        .map(|_| {
             let mut key_bytes = vec![0u8; algorithm.key_len()];
             rng.fill(key_bytes.as_mut_slice());
+            key_bytes
+        })
+        .collect();
 
-            let mut nonce_bytes = vec![0u8; algorithm.nonce_len()];
-            rng.fill(nonce_bytes.as_mut_slice());
+    let mut nonce_bytes = vec![0u8; algorithm.nonce_len()];
+    rng.fill(nonce_bytes.as_mut_slice());
 
-            let mut ctx = builder.nonce(&nonce_bytes).for_encryption(&key_bytes);
+    let mut pbuf = vec![0u8; len];
+    rng.fill(pbuf.as_mut_slice());
 
-            let pbuf = vec![0u8; *param];
-            let mut cbuf = vec![0u8; *param];
+    let mut cbuf = vec![0u8; len];
+    let mut tag = vec![0u8; algorithm.tag_len()];
 
+    group.bench_with_input(
+        criterion::BenchmarkId::new(format!("{}/rekey", algorithm.name()), interval),
+        &pbuf,
+        |b, param| {
+            let mut ctx = builder.nonce(&nonce_bytes).for_encryption(&keys[0]);
+            let mut generation = 0;
+            let mut remaining = interval;
+            let mut seq = 0u64;
             b.iter(|| {
-                ctx.encrypt(black_box(&pbuf), black_box(&mut cbuf));
+                if remaining == 0 {
+                    generation += 1;
+                    remaining = interval;
+                    let key_bytes = &keys[generation % keys.len()];
+                    ctx = builder.nonce(&nonce_bytes).for_encryption(key_bytes);
+                }
+                remaining -= 1;
+
+                let nonce_len = nonce_bytes.len();
+                nonce_bytes[nonce_len - 8..].copy_from_slice(&seq.to_be_bytes());
+                seq = seq.wrapping_add(1);
+                ctx.seal(
+                    black_box(&nonce_bytes),
+                    &[],
+                    black_box(param),
+                    black_box(&mut cbuf),
+                    black_box(&mut tag),
+                );
             });
         },
     );
 }
+
+/// Checks sealing against Test Case 4 of the GCM specification, after
+/// sealing another message to check that the nonce and the tag state are
+/// reset.
+pub fn check_seal<B>(mut builder: B)
+where
+    B: AeadBuilder,
+{
+    let key = [
+        0xfe, 0xff, 0xe9, 0x92, 0x86, 0x65, 0x73, 0x1c, 0x6d, 0x6a, 0x8f, 0x94, 0x67, 0x30, 0x83,
+        0x08,
+    ];
+    let nonce = [
+        0xca, 0xfe, 0xba, 0xbe, 0xfa, 0xce, 0xdb, 0xad, 0xde, 0xca, 0xf8, 0x88,
+    ];
+    let aad = [
+        0xfe, 0xed, 0xfa, 0xce, 0xde, 0xad, 0xbe, 0xef, 0xfe, 0xed, 0xfa, 0xce, 0xde, 0xad, 0xbe,
+        0xef, 0xab, 0xad, 0xda, 0xd2,
+    ];
+    let ptext = [
+        0xd9, 0x31, 0x32, 0x25, 0xf8, 0x84, 0x06, 0xe5, 0xa5, 0x59, 0x09, 0xc5, 0xaf, 0xf5, 0x26,
+        0x9a, 0x86, 0xa7, 0xa9, 0x53, 0x15, 0x34, 0xf7, 0xda, 0x2e, 0x4c, 0x30, 0x3d, 0x8a, 0x31,
+        0x8a, 0x72, 0x1c, 0x3c, 0x0c, 0x95, 0x95, 0x68, 0x09, 0x53, 0x2f, 0xcf, 0x0e, 0x24, 0x49,
+        0xa6, 0xb5, 0x25, 0xb1, 0x6a, 0xed, 0xf5, 0xaa, 0x0d, 0xe6, 0x57, 0xba, 0x63, 0x7b, 0x39,
+    ];
+    let ctext = [
+        0x42, 0x83, 0x1e, 0xc2, 0x21, 0x77, 0x74, 0x24, 0x4b, 0x72, 0x21, 0xb7, 0x84, 0xd0, 0xd4,
+        0x9c, 0xe3, 0xaa, 0x21, 0x2f, 0x2c, 0x02, 0xa4, 0xe0, 0x35, 0xc1, 0x7e, 0x23, 0x29, 0xac,
+        0xa1, 0x2e, 0x21, 0xd5, 0x14, 0xb2, 0x54, 0x66, 0x93, 0x1c, 0x7d, 0x8f, 0x6a, 0x5a, 0xac,
+        0x84, 0xaa, 0x05, 0x1b, 0xa3, 0x0b, 0x39, 0x6a, 0x0a, 0xac, 0x97, 0x3d, 0x58, 0xe0, 0x91,
+    ];
+    let tag = [
+        0x5b, 0xc9, 0x4f, 0xbc, 0x32, 0x21, 0xa5, 0xdb, 0x94, 0xfa, 0xe9, 0x5a, 0xe7, 0x12, 0x1a,
+        0x47,
+    ];
+
+    let mut ctx = builder.nonce(&[0u8; 12]).for_encryption(&key);
+
+    let mut out = [0u8; 60];
+    let mut out_tag = [0u8; 16];
+    ctx.seal(&[1u8; 12], &[], &[0u8; 60], &mut out, &mut out_tag);
+    ctx.seal(&nonce, &aad, &ptext, &mut out, &mut out_tag);
+
+    assert_eq!(out, ctext);
+    assert_eq!(out_tag, tag);
+}
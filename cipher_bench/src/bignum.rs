@@ -0,0 +1,83 @@
+// SPDX-License-Identifier: Apache-2.0
+
+pub enum BignumOperation {
+    ModExpConsttime,
+    ModMul,
+}
+
+impl BignumOperation {
+    pub fn name(&self) -> &str {
+        match self {
+            BignumOperation::ModExpConsttime => "modexp-consttime",
+            BignumOperation::ModMul => "modmul",
+        }
+    }
+}
+
+/// Modular arithmetic on operands loaded once, so that the conversion
+/// from bytes is not measured.
+pub trait Bignum {
+    /// Computes base^exponent mod modulus in constant time.
+    fn mod_exp(&mut self);
+    /// Computes base * multiplier mod modulus.
+    fn mod_mul(&mut self);
+}
+
+pub trait BignumBuilder {
+    /// All operands are big-endian. The modulus must be odd.
+    fn for_operands(
+        &mut self,
+        base: &[u8],
+        multiplier: &[u8],
+        exponent: &[u8],
+        modulus: &[u8],
+    ) -> Box<dyn Bignum>;
+}
+
+pub fn bench_bignum<B, M>(
+    group: &mut criterion::BenchmarkGroup<M>,
+    operation: BignumOperation,
+    mut builder: B,
+    bits: usize,
+) where
+    B: BignumBuilder,
+    M: criterion::measurement::Measurement,
+{
+    group.bench_with_input(
+        criterion::BenchmarkId::new(operation.name(), bits),
+        &bits,
+        |b, param| {
+            use rand::prelude::*;
+
+            let mut rng = rand::thread_rng();
+
+            let len = param.div_ceil(8);
+
+            // An odd modulus of exactly the requested size, like an RSA
+            // modulus or a DH prime.
+            let mut modulus = vec![0u8; len];
+            rng.fill(modulus.as_mut_slice());
+            modulus[0] |= 0x80;
+            modulus[len - 1] |= 1;
+
+            // Operands one bit shorter are always reduced.
+            let mut base = vec![0u8; len];
+            rng.fill(base.as_mut_slice());
+            base[0] &= 0x7f;
+
+            let mut multiplier = vec![0u8; len];
+            rng.fill(multiplier.as_mut_slice());
+            multiplier[0] &= 0x7f;
+
+            let mut exponent = vec![0u8; len];
+            rng.fill(exponent.as_mut_slice());
+
+            let mut ctx = builder.for_operands(&base, &multiplier, &exponent, &modulus);
+
+            match operation {
+                BignumOperation::ModExpConsttime => b.iter(|| ctx.mod_exp()),
+                BignumOperation::ModMul => b.iter(|| ctx.mod_mul()),
+            }
+        },
+    );
+}
@@ -27,6 +27,8 @@ impl BlockCipherAlgorithm {
 pub trait BlockCipher {
     fn encrypt(&mut self, ptext: &[u8], ctext: &mut [u8]);
     fn decrypt(&mut self, ctext: &[u8], ptext: &mut [u8]);
+    /// Restarts the context with a new nonce, keeping the key schedule.
+    fn set_nonce(&mut self, nonce: &[u8]);
 }
 
 pub trait BlockCipherBuilder {
@@ -35,40 +37,158 @@ pub trait BlockCipherBuilder {
     fn for_decryption(&mut self, key: &[u8]) -> Box<dyn BlockCipher>;
 }
 
+/// Benchmarks encryption and decryption of `len` bytes with a single key.
 pub fn bench_block<B, M>(
     group: &mut criterion::BenchmarkGroup<M>,
     algorithm: BlockCipherAlgorithm,
     mut builder: B,
-    count: usize,
+    len: usize,
 ) where
     B: BlockCipherBuilder,
     M: criterion::measurement::Measurement,
 {
-    let len = crate::STEP * count;
+    use criterion::black_box;
+    use rand::prelude::*;
+
+    let mut rng = rand::thread_rng();
+
+    let mut key_bytes = vec![0u8; algorithm.key_len()];
+    rng.fill(key_bytes.as_mut_slice());
+
+    let mut nonce_bytes = vec![0u8; algorithm.nonce_len()];
+    rng.fill(nonce_bytes.as_mut_slice());
+
+    let mut pbuf = vec![0u8; len];
+    rng.fill(pbuf.as_mut_slice());
+
+    // Decryption is fed real ciphertext, produced by a separate context
+    // so that the benchmarked ones start from the same state.
+    let mut cbuf = vec![0u8; len];
+    builder
+        .nonce(&nonce_bytes)
+        .for_encryption(&key_bytes)
+        .encrypt(&pbuf, &mut cbuf);
+
+    let mut ctx = builder.nonce(&nonce_bytes).for_encryption(&key_bytes);
+    let mut out = vec![0u8; len];
 
     group.bench_with_input(
-        criterion::BenchmarkId::new(algorithm.name(), count),
-        &len,
+        criterion::BenchmarkId::new(format!("{}/encrypt", algorithm.name()), len),
+        &pbuf,
         |b, param| {
-            use criterion::black_box;
-            use rand::prelude::*;
+            b.iter(|| {
+                ctx.encrypt(black_box(param), black_box(&mut out));
+            });
+        },
+    );
+
+    let mut ctx = builder.nonce(&nonce_bytes).for_decryption(&key_bytes);
 
-            let mut rng = rand::thread_rng();
+    group.bench_with_input(
+        criterion::BenchmarkId::new(format!("{}/decrypt", algorithm.name()), len),
+        &cbuf,
+        |b, param| {
+            b.iter(|| {
+                ctx.decrypt(black_box(param), black_box(&mut out));
+            });
+        },
+    );
+}
 
-            let mut key_bytes = vec![0u8; algorithm.key_len()];
-            rng.fill(key_bytes.as_mut_slice());
+/// Registers the cases of [`bench_block`] with a Callgrind runner, each
+/// encrypting or decrypting `len` bytes once. The inputs come from a
+/// fixed seed, so that runs are repeatable.
+pub fn callgrind_block<B>(
+    runner: &mut crate::Callgrind,
+    algorithm: BlockCipherAlgorithm,
+    mut builder: B,
+    len: usize,
+) where
+    B: BlockCipherBuilder,
+{
+    use criterion::black_box;
+    use rand::prelude::*;
 
-            let mut nonce_bytes = vec![0u8; algorithm.nonce_len()];
-            rng.fill(nonce_bytes.as_mut_slice());
+    let mut rng = StdRng::seed_from_u64(0);
 
-            let mut ctx = builder.nonce(&nonce_bytes).for_encryption(&key_bytes);
+    let mut key_bytes = vec![0u8; algorithm.key_len()];
+    rng.fill(key_bytes.as_mut_slice());
 
-            let pbuf = vec![0u8; *param];
-            let mut cbuf = vec![0u8; *param];
+    let mut nonce_bytes = vec![0u8; algorithm.nonce_len()];
+    rng.fill(nonce_bytes.as_mut_slice());
 
-            b.iter(|| {
-                ctx.encrypt(black_box(&pbuf), black_box(&mut cbuf));
+    let mut pbuf = vec![0u8; len];
+    rng.fill(pbuf.as_mut_slice());
+
+    let mut cbuf = vec![0u8; len];
+    builder
+        .nonce(&nonce_bytes)
+        .for_encryption(&key_bytes)
+        .encrypt(&pbuf, &mut cbuf);
+
+    let mut ctx = builder.nonce(&nonce_bytes).for_encryption(&key_bytes);
+    let mut out = vec![0u8; len];
+    runner.case(format!("{}/encrypt/{}", algorithm.name(), len), move || {
+        ctx.encrypt(black_box(&pbuf), black_box(&mut out));
+    });
+
+    let mut ctx = builder.nonce(&nonce_bytes).for_decryption(&key_bytes);
+    let mut out = vec![0u8; len];
+    runner.case(format!("{}/decrypt/{}", algorithm.name(), len), move || {
+        ctx.decrypt(black_box(&cbuf), black_box(&mut out));
+    });
+}
+
+/// Benchmarks creating encryption and decryption contexts, which runs the
+/// key schedule, and restarting an existing context with a new nonce.
+/// Dropping the contexts is not timed.
+pub fn bench_block_setup<B, M>(
+    group: &mut criterion::BenchmarkGroup<M>,
+    algorithm: BlockCipherAlgorithm,
+    mut builder: B,
+) where
+    B: BlockCipherBuilder,
+    M: criterion::measurement::Measurement,
+{
+    use criterion::black_box;
+    use rand::prelude::*;
+
+    let mut rng = rand::thread_rng();
+
+    let mut key_bytes = vec![0u8; algorithm.key_len()];
+    rng.fill(key_bytes.as_mut_slice());
+
+    let mut nonce_bytes = vec![0u8; algorithm.nonce_len()];
+    rng.fill(nonce_bytes.as_mut_slice());
+
+    group.bench_function(
+        criterion::BenchmarkId::new(algorithm.name(), "for-encryption"),
+        |b| {
+            b.iter_with_large_drop(|| {
+                builder
+                    .nonce(black_box(&nonce_bytes))
+                    .for_encryption(black_box(&key_bytes))
+            });
+        },
+    );
+
+    group.bench_function(
+        criterion::BenchmarkId::new(algorithm.name(), "for-decryption"),
+        |b| {
+            b.iter_with_large_drop(|| {
+                builder
+                    .nonce(black_box(&nonce_bytes))
+                    .for_decryption(black_box(&key_bytes))
             });
         },
     );
+
+    let mut ctx = builder.nonce(&nonce_bytes).for_encryption(&key_bytes);
+
+    group.bench_function(
+        criterion::BenchmarkId::new(algorithm.name(), "set-nonce"),
+        |b| {
+            b.iter(|| ctx.set_nonce(black_box(&nonce_bytes)));
+        },
+    );
 }
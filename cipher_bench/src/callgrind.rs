@@ -0,0 +1,262 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// An iai-style runner: each registered case is run exactly once in a
+/// copy of the bench executable started under Callgrind, which counts
+/// the instructions and simulates the caches for the case alone, leaving
+/// out the setup.
+///
+/// Results are written to `target/callgrind/<group>/` and compared with
+/// those of the previous run. Like criterion, arguments that are not
+/// options filter the cases by substring.
+pub struct Callgrind {
+    group: String,
+    cases: Vec<(String, Box<dyn FnMut()>)>,
+}
+
+/// The argument the runner passes to the executable it starts under
+/// Callgrind, followed by the name of the case to run.
+const RUN_ARG: &str = "--callgrind-run";
+
+/// Only this function is instrumented.
+const TOGGLE_COLLECT: &str = "*cipher_bench::callgrind::run_case*";
+
+#[inline(never)]
+fn run_case(case: &mut dyn FnMut()) {
+    case();
+}
+
+impl Callgrind {
+    pub fn new(group: &str) -> Self {
+        Self {
+            group: group.to_string(),
+            cases: Vec::new(),
+        }
+    }
+
+    pub fn case<F>(&mut self, name: String, case: F)
+    where
+        F: FnMut() + 'static,
+    {
+        self.cases.push((name, Box::new(case)));
+    }
+
+    pub fn run(mut self) {
+        let mut args = env::args();
+        let executable = args.next().unwrap();
+        let args: Vec<String> = args.collect();
+
+        if let Some(index) = args.iter().position(|arg| arg == RUN_ARG) {
+            let name = &args[index + 1];
+            let (_, case) = self
+                .cases
+                .iter_mut()
+                .find(|(case_name, _)| case_name == name)
+                .unwrap_or_else(|| panic!("unknown case `{}`", name));
+            run_case(case);
+            return;
+        }
+
+        if let Err(e) = Command::new("valgrind")
+            .arg("--version")
+            .stdout(Stdio::null())
+            .status()
+        {
+            println!("valgrind unavailable ({}), skipping {}", e, self.group);
+            return;
+        }
+
+        let filters: Vec<&String> = args.iter().filter(|arg| !arg.starts_with('-')).collect();
+        let dir = target_dir(&executable).join("callgrind").join(&self.group);
+        fs::create_dir_all(&dir).unwrap();
+
+        for (name, _) in &self.cases {
+            if !filters.is_empty() && !filters.iter().any(|filter| name.contains(*filter)) {
+                continue;
+            }
+
+            // The previous results are only rotated once the run has
+            // succeeded, so a failed run keeps them for the next one.
+            let path = dir.join(format!("{}.out", name.replace('/', "_")));
+            let old_path = path.with_extension("out.old");
+            let new_path = path.with_extension("out.new");
+
+            let status = Command::new("valgrind")
+                .arg("--tool=callgrind")
+                .arg("--cache-sim=yes")
+                // Fixed cache sizes, so that results do not depend on the
+                // host's caches.
+                .arg("--I1=32768,8,64")
+                .arg("--D1=32768,8,64")
+                .arg("--LL=8388608,16,64")
+                .arg("--collect-atstart=no")
+                .arg(format!("--toggle-collect={}", TOGGLE_COLLECT))
+                .arg(format!("--callgrind-out-file={}", new_path.display()))
+                .arg(&executable)
+                .arg(RUN_ARG)
+                .arg(name)
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .status()
+                .expect("cannot run valgrind");
+            assert!(status.success(), "{} failed under callgrind", name);
+
+            let stats = CallgrindStats::read(&new_path).unwrap();
+            let old_stats = CallgrindStats::read(&path).ok();
+            let old = old_stats.as_ref();
+
+            if path.exists() {
+                fs::rename(&path, &old_path).unwrap();
+            }
+            fs::rename(&new_path, &path).unwrap();
+
+            println!("{}/{}", self.group, name);
+            let rows = [
+                (
+                    "Instructions",
+                    stats.instructions(),
+                    old.map(CallgrindStats::instructions),
+                ),
+                ("L1 hits", stats.l1_hits(), old.map(CallgrindStats::l1_hits)),
+                ("LL hits", stats.ll_hits(), old.map(CallgrindStats::ll_hits)),
+                (
+                    "RAM hits",
+                    stats.ram_hits(),
+                    old.map(CallgrindStats::ram_hits),
+                ),
+                (
+                    "Estimated cycles",
+                    stats.estimated_cycles(),
+                    old.map(CallgrindStats::estimated_cycles),
+                ),
+            ];
+            for (label, new, old) in rows.iter() {
+                let change = match old {
+                    Some(old) => format!(" ({})", percent_change(*old, *new)),
+                    None => String::new(),
+                };
+                println!("  {:<18}{:>15}{}", label, new, change);
+            }
+            println!();
+        }
+    }
+}
+
+/// Finds cargo's target directory from the bench executable, which is
+/// `target/<profile>/deps/<name>-<hash>`.
+fn target_dir(executable: &str) -> PathBuf {
+    match env::var_os("CARGO_TARGET_DIR") {
+        Some(dir) => PathBuf::from(dir),
+        None => Path::new(executable)
+            .ancestors()
+            .nth(3)
+            .unwrap()
+            .to_path_buf(),
+    }
+}
+
+fn percent_change(old: u64, new: u64) -> String {
+    if old == new {
+        return "no change".to_string();
+    }
+    format!("{:+.3}%", (new as f64 - old as f64) * 100.0 / old as f64)
+}
+
+/// The event totals of a Callgrind output file.
+#[derive(Debug)]
+struct CallgrindStats {
+    events: HashMap<String, u64>,
+}
+
+impl CallgrindStats {
+    fn read(path: &Path) -> io::Result<Self> {
+        let output = fs::read_to_string(path)?;
+        Self::parse(&output)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "no event totals"))
+    }
+
+    fn parse(output: &str) -> Option<Self> {
+        let mut names = None;
+        let mut totals = None;
+        for line in output.lines() {
+            if let Some(line) = line.strip_prefix("events:") {
+                names = Some(line);
+            } else if let Some(line) = line
+                .strip_prefix("totals:")
+                .or_else(|| line.strip_prefix("summary:"))
+            {
+                totals = Some(line);
+            }
+        }
+
+        let events = names?
+            .split_whitespace()
+            .map(str::to_string)
+            .zip(totals?.split_whitespace().map(|total| total.parse().ok()))
+            .map(|(name, total)| Some((name, total?)))
+            .collect::<Option<_>>()?;
+        Some(Self { events })
+    }
+
+    fn event(&self, name: &str) -> u64 {
+        self.events.get(name).copied().unwrap_or(0)
+    }
+
+    fn instructions(&self) -> u64 {
+        self.event("Ir")
+    }
+
+    fn accesses(&self) -> u64 {
+        self.event("Ir") + self.event("Dr") + self.event("Dw")
+    }
+
+    fn l1_misses(&self) -> u64 {
+        self.event("I1mr") + self.event("D1mr") + self.event("D1mw")
+    }
+
+    fn ram_hits(&self) -> u64 {
+        self.event("ILmr") + self.event("DLmr") + self.event("DLmw")
+    }
+
+    fn ll_hits(&self) -> u64 {
+        self.l1_misses() - self.ram_hits()
+    }
+
+    fn l1_hits(&self) -> u64 {
+        self.accesses() - self.l1_misses()
+    }
+
+    /// The cost model iai uses, from
+    /// <https://pythonspeed.com/articles/consistent-benchmarking-in-ci/>.
+    fn estimated_cycles(&self) -> u64 {
+        self.l1_hits() + 5 * self.ll_hits() + 35 * self.ram_hits()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_totals() {
+        let output = "# callgrind format\nversion: 1\n\
+                      events: Ir Dr Dw I1mr D1mr D1mw ILmr DLmr DLmw\n\
+                      fn=(1) cipher_bench::callgrind::run_case\n\
+                      0 1000 300 200 10 20 5 2 3 1\n\
+                      totals: 1000 300 200 10 20 5 2 3 1\n";
+        let stats = CallgrindStats::parse(output).unwrap();
+        assert_eq!(stats.instructions(), 1000);
+        assert_eq!(stats.ram_hits(), 6);
+        assert_eq!(stats.ll_hits(), 29);
+        assert_eq!(stats.l1_hits(), 1465);
+        assert_eq!(stats.estimated_cycles(), 1465 + 5 * 29 + 35 * 6);
+
+        assert!(CallgrindStats::parse("events: Ir\n").is_none());
+    }
+}
@@ -0,0 +1,149 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use criterion::measurement::{Measurement, ValueFormatter};
+use criterion::Throughput;
+
+/// Measures in ticks of the CPU's cycle counter: the TSC on x86_64, read
+/// with `rdtsc` and `rdtscp` fenced by `lfence`, and `cntvct_el0` on
+/// aarch64.
+///
+/// On x86_64 the TSC ticks at a constant reference frequency, so it only
+/// matches core cycles with frequency scaling and turbo disabled. On
+/// aarch64 `cntvct_el0` is the generic timer, which usually runs well
+/// below the core clock; its values are comparable between runs on the
+/// same machine only.
+pub struct Cycles;
+
+#[cfg(target_arch = "x86_64")]
+#[inline(always)]
+fn start_counter() -> u64 {
+    use std::arch::x86_64::{_mm_lfence, _rdtsc};
+
+    // The fences keep earlier and later instructions from being
+    // reordered around the read.
+    unsafe {
+        _mm_lfence();
+        let tsc = _rdtsc();
+        _mm_lfence();
+        tsc
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+#[inline(always)]
+fn end_counter() -> u64 {
+    use std::arch::x86_64::{__rdtscp, _mm_lfence};
+
+    // rdtscp waits for the preceding instructions to complete.
+    unsafe {
+        let mut aux = 0;
+        let tsc = __rdtscp(&mut aux);
+        _mm_lfence();
+        tsc
+    }
+}
+
+#[cfg(target_arch = "aarch64")]
+#[inline(always)]
+fn read_cntvct() -> u64 {
+    let value: u64;
+    unsafe {
+        std::arch::asm!("isb", "mrs {}, cntvct_el0", out(reg) value, options(nostack));
+    }
+    value
+}
+
+#[cfg(target_arch = "aarch64")]
+#[inline(always)]
+fn start_counter() -> u64 {
+    read_cntvct()
+}
+
+#[cfg(target_arch = "aarch64")]
+#[inline(always)]
+fn end_counter() -> u64 {
+    read_cntvct()
+}
+
+#[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+fn start_counter() -> u64 {
+    panic!("no cycle counter on this architecture")
+}
+
+#[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+fn end_counter() -> u64 {
+    panic!("no cycle counter on this architecture")
+}
+
+impl Measurement for Cycles {
+    type Intermediate = u64;
+    type Value = u64;
+
+    fn start(&self) -> Self::Intermediate {
+        start_counter()
+    }
+
+    fn end(&self, i: Self::Intermediate) -> Self::Value {
+        end_counter().wrapping_sub(i)
+    }
+
+    fn add(&self, v1: &Self::Value, v2: &Self::Value) -> Self::Value {
+        v1 + v2
+    }
+
+    fn zero(&self) -> Self::Value {
+        0
+    }
+
+    fn to_f64(&self, value: &Self::Value) -> f64 {
+        *value as f64
+    }
+
+    fn formatter(&self) -> &dyn ValueFormatter {
+        &CyclesFormatter
+    }
+}
+
+/// Formats cycle counts, and throughputs as cycles per byte or per
+/// element, where lower is better.
+pub(crate) struct CyclesFormatter;
+
+impl ValueFormatter for CyclesFormatter {
+    fn scale_values(&self, typical_value: f64, values: &mut [f64]) -> &'static str {
+        let (factor, unit) = if typical_value < 1e4 {
+            (1.0, "cycles")
+        } else if typical_value < 1e7 {
+            (1e-3, "Kcycles")
+        } else {
+            (1e-6, "Mcycles")
+        };
+        for value in values {
+            *value *= factor;
+        }
+        unit
+    }
+
+    fn scale_throughputs(
+        &self,
+        typical_value: f64,
+        throughput: &Throughput,
+        values: &mut [f64],
+    ) -> &'static str {
+        let (count, unit) = match *throughput {
+            Throughput::Bytes(bytes) => (bytes, "cycles/B"),
+            Throughput::Elements(elements) => (elements, "cycles/elem"),
+        };
+        // An empty message has no cost per byte.
+        if count == 0 {
+            return self.scale_values(typical_value, values);
+        }
+        for value in values {
+            *value /= count as f64;
+        }
+        unit
+    }
+
+    fn scale_for_machines(&self, _values: &mut [f64]) -> &'static str {
+        "cycles"
+    }
+}
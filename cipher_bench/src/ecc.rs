@@ -0,0 +1,56 @@
+// SPDX-License-Identifier: Apache-2.0
+
+pub enum CurveAlgorithm {
+    P256,
+    P384,
+    Curve25519,
+}
+
+impl CurveAlgorithm {
+    pub fn name(&self) -> &str {
+        match self {
+            CurveAlgorithm::P256 => "p256",
+            CurveAlgorithm::P384 => "p384",
+            CurveAlgorithm::Curve25519 => "curve25519",
+        }
+    }
+}
+
+/// Scalar multiplication with a random scalar, keeping the result inside
+/// the implementation's own point representation.
+pub trait ScalarMul {
+    /// Multiplies the generator, which may use precomputed tables.
+    fn mul_g(&mut self);
+    /// Multiplies an arbitrary point.
+    fn mul(&mut self);
+}
+
+pub trait ScalarMulBuilder {
+    /// Picks a random scalar and a random point.
+    fn generate(&mut self) -> Box<dyn ScalarMul>;
+}
+
+pub fn bench_scalar_mul<B, M>(
+    group: &mut criterion::BenchmarkGroup<M>,
+    curve: CurveAlgorithm,
+    mut builder: B,
+) where
+    B: ScalarMulBuilder,
+    M: criterion::measurement::Measurement,
+{
+    let mut ctx = builder.generate();
+
+    group.bench_function(
+        criterion::BenchmarkId::new(curve.name(), "fixed-base"),
+        |b| {
+            b.iter(|| ctx.mul_g());
+        },
+    );
+
+    group.bench_function(
+        criterion::BenchmarkId::new(curve.name(), "variable-base"),
+        |b| {
+            b.iter(|| ctx.mul());
+        },
+    );
+}
@@ -0,0 +1,75 @@
+// SPDX-License-Identifier: Apache-2.0
+
+pub enum HpkeAlgorithm {
+    X25519Sha256Aes128Gcm,
+    X25519Sha256ChaCha20Poly1305,
+}
+
+impl HpkeAlgorithm {
+    pub fn name(&self) -> &str {
+        match self {
+            HpkeAlgorithm::X25519Sha256Aes128Gcm => "dhkem-x25519-hkdf-sha256-aes-128-gcm",
+            HpkeAlgorithm::X25519Sha256ChaCha20Poly1305 => {
+                "dhkem-x25519-hkdf-sha256-chacha20-poly1305"
+            }
+        }
+    }
+}
+
+/// Single-shot HPKE in base mode, where every seal and open performs the
+/// full sender or receiver setup.
+pub trait Hpke {
+    /// Returns the encoded public key of the recipient.
+    fn public_key(&self) -> Vec<u8>;
+    /// Encrypts to the peer's encoded public key, returning the
+    /// encapsulated key and the ciphertext.
+    fn seal(&mut self, peer: &[u8], info: &[u8], aad: &[u8], ptext: &[u8]) -> (Vec<u8>, Vec<u8>);
+    /// Decrypts a ciphertext with this recipient's private key.
+    fn open(&mut self, enc: &[u8], info: &[u8], aad: &[u8], ctext: &[u8]) -> Vec<u8>;
+}
+
+pub trait HpkeBuilder {
+    /// Generates a new recipient key pair.
+    fn generate(&mut self) -> Box<dyn Hpke>;
+}
+
+pub fn bench_hpke<B, M>(
+    group: &mut criterion::BenchmarkGroup<M>,
+    algorithm: HpkeAlgorithm,
+    mut builder: B,
+    len: usize,
+) where
+    B: HpkeBuilder,
+    M: criterion::measurement::Measurement,
+{
+    use criterion::black_box;
+    use rand::prelude::*;
+
+    let mut rng = rand::thread_rng();
+
+    let mut pbuf = vec![0u8; len];
+    rng.fill(pbuf.as_mut_slice());
+
+    let info = b"cipher_bench hpke";
+    let aad = [0u8; 16];
+
+    let mut ctx = builder.generate();
+    let peer = ctx.public_key();
+    let (enc, cbuf) = ctx.seal(&peer, info, &aad, &pbuf);
+
+    group.bench_with_input(
+        criterion::BenchmarkId::new(format!("{}/seal", algorithm.name()), len),
+        &pbuf,
+        |b, param| {
+            b.iter(|| ctx.seal(black_box(&peer), info, &aad, black_box(param)));
+        },
+    );
+
+    group.bench_with_input(
+        criterion::BenchmarkId::new(format!("{}/open", algorithm.name()), len),
+        &cbuf,
+        |b, param| {
+            b.iter(|| ctx.open(black_box(&enc), info, &aad, black_box(param)));
+        },
+    );
+}
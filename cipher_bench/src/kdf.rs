@@ -0,0 +1,425 @@
+// SPDX-License-Identifier: Apache-2.0
+
+pub enum KdfAlgorithm {
+    HkdfSha256,
+    HkdfSha384,
+}
+
+impl KdfAlgorithm {
+    pub fn name(&self) -> &str {
+        match self {
+            KdfAlgorithm::HkdfSha256 => "hkdf-sha256",
+            KdfAlgorithm::HkdfSha384 => "hkdf-sha384",
+        }
+    }
+
+    pub fn hash_len(&self) -> usize {
+        match self {
+            KdfAlgorithm::HkdfSha256 => 32,
+            KdfAlgorithm::HkdfSha384 => 48,
+        }
+    }
+}
+
+/// A two-step key derivation function: an HMAC-based randomness
+/// extraction step followed by a key expansion step (RFC 5869).
+pub trait Kdf {
+    fn extract(&mut self, salt: &[u8], ikm: &[u8], prk: &mut [u8]);
+    fn expand(&mut self, prk: &[u8], info: &[u8], okm: &mut [u8]);
+}
+
+pub trait KdfBuilder {
+    fn for_derivation(&mut self) -> Box<dyn Kdf>;
+}
+
+pub fn bench_kdf<B, M>(
+    group: &mut criterion::BenchmarkGroup<M>,
+    algorithm: KdfAlgorithm,
+    mut builder: B,
+    len: usize,
+) where
+    B: KdfBuilder,
+    M: criterion::measurement::Measurement,
+{
+    group.bench_with_input(
+        criterion::BenchmarkId::new(algorithm.name(), len),
+        &len,
+        |b, param| {
+            use criterion::black_box;
+            use rand::prelude::*;
+
+            let mut rng = rand::thread_rng();
+
+            let mut salt_bytes = vec![0u8; algorithm.hash_len()];
+            rng.fill(salt_bytes.as_mut_slice());
+
+            let mut ikm_bytes = vec![0u8; algorithm.hash_len()];
+            rng.fill(ikm_bytes.as_mut_slice());
+
+            let mut info_bytes = vec![0u8; algorithm.hash_len()];
+            rng.fill(info_bytes.as_mut_slice());
+
+            let mut ctx = builder.for_derivation();
+
+            let mut prk = vec![0u8; algorithm.hash_len()];
+            let mut okm = vec![0u8; *param];
+
+            b.iter(|| {
+                ctx.extract(
+                    black_box(&salt_bytes),
+                    black_box(&ikm_bytes),
+                    black_box(&mut prk),
+                );
+                ctx.expand(black_box(&prk), black_box(&info_bytes), black_box(&mut okm));
+            });
+        },
+    );
+}
+
+pub enum KbkdfAlgorithm {
+    CounterHmacSha256,
+}
+
+impl KbkdfAlgorithm {
+    pub fn name(&self) -> &str {
+        match self {
+            KbkdfAlgorithm::CounterHmacSha256 => "kbkdf-counter-hmac-sha256",
+        }
+    }
+
+    pub fn key_len(&self) -> usize {
+        match self {
+            KbkdfAlgorithm::CounterHmacSha256 => 32,
+        }
+    }
+}
+
+/// A single-step key-based key derivation function (SP 800-108), keyed
+/// directly with a key derivation key. `fixed_input` is the data that
+/// follows the counter in every PRF input, see [`kbkdf_fixed_input`].
+pub trait Kbkdf {
+    fn derive(&mut self, key: &[u8], fixed_input: &[u8], okm: &mut [u8]);
+}
+
+pub trait KbkdfBuilder {
+    fn for_derivation(&mut self) -> Box<dyn Kbkdf>;
+}
+
+/// Encodes the fixed input recommended by SP 800-108, section 5:
+/// `label || 0x00 || context || [L]_32`, with L the output length in bits.
+pub fn kbkdf_fixed_input(label: &[u8], context: &[u8], okm_len: usize) -> Vec<u8> {
+    let mut fixed_input = Vec::with_capacity(label.len() + 1 + context.len() + 4);
+    fixed_input.extend_from_slice(label);
+    fixed_input.push(0);
+    fixed_input.extend_from_slice(context);
+    fixed_input.extend_from_slice(&(okm_len as u32 * 8).to_be_bytes());
+    fixed_input
+}
+
+pub fn bench_kbkdf<B, M>(
+    group: &mut criterion::BenchmarkGroup<M>,
+    algorithm: KbkdfAlgorithm,
+    mut builder: B,
+    len: usize,
+) where
+    B: KbkdfBuilder,
+    M: criterion::measurement::Measurement,
+{
+    group.bench_with_input(
+        criterion::BenchmarkId::new(algorithm.name(), len),
+        &len,
+        |b, param| {
+            use criterion::black_box;
+            use rand::prelude::*;
+
+            let mut rng = rand::thread_rng();
+
+            let mut key_bytes = vec![0u8; algorithm.key_len()];
+            rng.fill(key_bytes.as_mut_slice());
+
+            let mut context_bytes = vec![0u8; algorithm.key_len()];
+            rng.fill(context_bytes.as_mut_slice());
+
+            let fixed_input = kbkdf_fixed_input(b"cipher_bench", &context_bytes, *param);
+
+            let mut ctx = builder.for_derivation();
+
+            let mut okm = vec![0u8; *param];
+
+            b.iter(|| {
+                ctx.derive(
+                    black_box(&key_bytes),
+                    black_box(&fixed_input),
+                    black_box(&mut okm),
+                );
+            });
+        },
+    );
+}
+
+/// HKDF-Expand-Label from RFC 8446, section 7.1.
+pub fn hkdf_expand_label(
+    kdf: &mut dyn Kdf,
+    secret: &[u8],
+    label: &[u8],
+    context: &[u8],
+    okm: &mut [u8],
+) {
+    let mut info = Vec::with_capacity(4 + 6 + label.len() + context.len());
+    info.extend_from_slice(&(okm.len() as u16).to_be_bytes());
+    info.push((6 + label.len()) as u8);
+    info.extend_from_slice(b"tls13 ");
+    info.extend_from_slice(label);
+    info.push(context.len() as u8);
+    info.extend_from_slice(context);
+    kdf.expand(secret, &info, okm);
+}
+
+/// Traffic secrets and keys produced by [`tls13_key_schedule`].
+pub struct Tls13Secrets {
+    pub client_handshake_traffic_secret: Vec<u8>,
+    pub server_handshake_traffic_secret: Vec<u8>,
+    pub client_application_traffic_secret: Vec<u8>,
+    pub server_application_traffic_secret: Vec<u8>,
+    pub exporter_master_secret: Vec<u8>,
+    pub resumption_master_secret: Vec<u8>,
+    pub client_handshake_key: Vec<u8>,
+    pub client_handshake_iv: Vec<u8>,
+    pub server_handshake_key: Vec<u8>,
+    pub server_handshake_iv: Vec<u8>,
+    pub client_application_key: Vec<u8>,
+    pub client_application_iv: Vec<u8>,
+    pub server_application_key: Vec<u8>,
+    pub server_application_iv: Vec<u8>,
+    pub client_finished_key: Vec<u8>,
+    pub server_finished_key: Vec<u8>,
+}
+
+/// Replays the TLS 1.3 key schedule (RFC 8446, section 7.1) of a full
+/// handshake without PSK, from the (EC)DHE shared secret up to the
+/// application traffic keys.
+///
+/// Transcript hashing is not part of the KDF cost, so the transcript
+/// hashes are supplied by the caller: `empty_hash` is Hash(""), and
+/// the others cover the handshake up to ServerHello, server Finished
+/// and client Finished respectively.
+pub fn tls13_key_schedule(
+    kdf: &mut dyn Kdf,
+    shared_secret: &[u8],
+    empty_hash: &[u8],
+    server_hello_hash: &[u8],
+    server_finished_hash: &[u8],
+    client_finished_hash: &[u8],
+    key_len: usize,
+) -> Tls13Secrets {
+    let hash_len = empty_hash.len();
+    let zeros = vec![0u8; hash_len];
+
+    let derive_secret = |kdf: &mut dyn Kdf, secret: &[u8], label: &[u8], hash: &[u8]| {
+        let mut out = vec![0u8; hash_len];
+        hkdf_expand_label(kdf, secret, label, hash, &mut out);
+        out
+    };
+
+    let mut early_secret = vec![0u8; hash_len];
+    kdf.extract(&zeros, &zeros, &mut early_secret);
+    let derived = derive_secret(kdf, &early_secret, b"derived", empty_hash);
+
+    let mut handshake_secret = vec![0u8; hash_len];
+    kdf.extract(&derived, shared_secret, &mut handshake_secret);
+    let client_handshake_traffic_secret =
+        derive_secret(kdf, &handshake_secret, b"c hs traffic", server_hello_hash);
+    let server_handshake_traffic_secret =
+        derive_secret(kdf, &handshake_secret, b"s hs traffic", server_hello_hash);
+    let derived = derive_secret(kdf, &handshake_secret, b"derived", empty_hash);
+
+    let mut master_secret = vec![0u8; hash_len];
+    kdf.extract(&derived, &zeros, &mut master_secret);
+    let client_application_traffic_secret =
+        derive_secret(kdf, &master_secret, b"c ap traffic", server_finished_hash);
+    let server_application_traffic_secret =
+        derive_secret(kdf, &master_secret, b"s ap traffic", server_finished_hash);
+    let exporter_master_secret =
+        derive_secret(kdf, &master_secret, b"exp master", server_finished_hash);
+    let resumption_master_secret =
+        derive_secret(kdf, &master_secret, b"res master", client_finished_hash);
+
+    let traffic_key = |kdf: &mut dyn Kdf, secret: &[u8]| {
+        let mut key = vec![0u8; key_len];
+        hkdf_expand_label(kdf, secret, b"key", b"", &mut key);
+        let mut iv = vec![0u8; 12];
+        hkdf_expand_label(kdf, secret, b"iv", b"", &mut iv);
+        (key, iv)
+    };
+
+    let (client_handshake_key, client_handshake_iv) =
+        traffic_key(kdf, &client_handshake_traffic_secret);
+    let (server_handshake_key, server_handshake_iv) =
+        traffic_key(kdf, &server_handshake_traffic_secret);
+    let (client_application_key, client_application_iv) =
+        traffic_key(kdf, &client_application_traffic_secret);
+    let (server_application_key, server_application_iv) =
+        traffic_key(kdf, &server_application_traffic_secret);
+
+    let mut client_finished_key = vec![0u8; hash_len];
+    hkdf_expand_label(
+        kdf,
+        &client_handshake_traffic_secret,
+        b"finished",
+        b"",
+        &mut client_finished_key,
+    );
+    let mut server_finished_key = vec![0u8; hash_len];
+    hkdf_expand_label(
+        kdf,
+        &server_handshake_traffic_secret,
+        b"finished",
+        b"",
+        &mut server_finished_key,
+    );
+
+    Tls13Secrets {
+        client_handshake_traffic_secret,
+        server_handshake_traffic_secret,
+        client_application_traffic_secret,
+        server_application_traffic_secret,
+        exporter_master_secret,
+        resumption_master_secret,
+        client_handshake_key,
+        client_handshake_iv,
+        server_handshake_key,
+        server_handshake_iv,
+        client_application_key,
+        client_application_iv,
+        server_application_key,
+        server_application_iv,
+        client_finished_key,
+        server_finished_key,
+    }
+}
+
+pub fn bench_tls13_key_schedule<B, M>(
+    group: &mut criterion::BenchmarkGroup<M>,
+    algorithm: KdfAlgorithm,
+    mut builder: B,
+    key_len: usize,
+) where
+    B: KdfBuilder,
+    M: criterion::measurement::Measurement,
+{
+    group.bench_function(
+        criterion::BenchmarkId::new("tls13-key-schedule", algorithm.name()),
+        |b| {
+            use criterion::black_box;
+            use rand::prelude::*;
+
+            let mut rng = rand::thread_rng();
+
+            let mut shared_secret = vec![0u8; algorithm.hash_len()];
+            rng.fill(shared_secret.as_mut_slice());
+
+            let mut hashes = vec![vec![0u8; algorithm.hash_len()]; 4];
+            for hash in hashes.iter_mut() {
+                rng.fill(hash.as_mut_slice());
+            }
+
+            let mut ctx = builder.for_derivation();
+
+            b.iter(|| {
+                tls13_key_schedule(
+                    ctx.as_mut(),
+                    black_box(&shared_secret),
+                    black_box(&hashes[0]),
+                    black_box(&hashes[1]),
+                    black_box(&hashes[2]),
+                    black_box(&hashes[3]),
+                    key_len,
+                )
+            });
+        },
+    );
+}
+
+/// Checks an HKDF-SHA256 implementation against the key schedule of the
+/// simple 1-RTT handshake in RFC 8448, section 3.
+pub fn check_tls13_rfc8448<B>(mut builder: B)
+where
+    B: KdfBuilder,
+{
+    let shared_secret = [
+        0x8b, 0xd4, 0x05, 0x4f, 0xb5, 0x5b, 0x9d, 0x63, 0xfd, 0xfb, 0xac, 0xf9, 0xf0, 0x4b, 0x9f,
+        0x0d, 0x35, 0xe6, 0xd6, 0x3f, 0x53, 0x75, 0x63, 0xef, 0xd4, 0x62, 0x72, 0x90, 0x0f, 0x89,
+        0x49, 0x2d,
+    ];
+    let empty_hash = [
+        0xe3, 0xb0, 0xc4, 0x42, 0x98, 0xfc, 0x1c, 0x14, 0x9a, 0xfb, 0xf4, 0xc8, 0x99, 0x6f, 0xb9,
+        0x24, 0x27, 0xae, 0x41, 0xe4, 0x64, 0x9b, 0x93, 0x4c, 0xa4, 0x95, 0x99, 0x1b, 0x78, 0x52,
+        0xb8, 0x55,
+    ];
+    let server_hello_hash = [
+        0x86, 0x0c, 0x06, 0xed, 0xc0, 0x78, 0x58, 0xee, 0x8e, 0x78, 0xf0, 0xe7, 0x42, 0x8c, 0x58,
+        0xed, 0xd6, 0xb4, 0x3f, 0x2c, 0xa3, 0xe6, 0xe9, 0x5f, 0x02, 0xed, 0x06, 0x3c, 0xf0, 0xe1,
+        0xca, 0xd8,
+    ];
+    let client_handshake_traffic_secret = [
+        0xb3, 0xed, 0xdb, 0x12, 0x6e, 0x06, 0x7f, 0x35, 0xa7, 0x80, 0xb3, 0xab, 0xf4, 0x5e, 0x2d,
+        0x8f, 0x3b, 0x1a, 0x95, 0x07, 0x38, 0xf5, 0x2e, 0x96, 0x00, 0x74, 0x6a, 0x0e, 0x27, 0xa5,
+        0x5a, 0x21,
+    ];
+    let client_handshake_key = [
+        0xdb, 0xfa, 0xa6, 0x93, 0xd1, 0x76, 0x2c, 0x5b, 0x66, 0x6a, 0xf5, 0xd9, 0x50, 0x25, 0x8d,
+        0x01,
+    ];
+    let client_handshake_iv = [
+        0x5b, 0xd3, 0xc7, 0x1b, 0x83, 0x6e, 0x0b, 0x76, 0xbb, 0x73, 0x26, 0x5f,
+    ];
+
+    // Only the handshake secrets are checked, which do not depend on the
+    // later transcript hashes.
+    let mut ctx = builder.for_derivation();
+    let secrets = tls13_key_schedule(
+        ctx.as_mut(),
+        &shared_secret,
+        &empty_hash,
+        &server_hello_hash,
+        &empty_hash,
+        &empty_hash,
+        16,
+    );
+
+    assert_eq!(
+        secrets.client_handshake_traffic_secret,
+        client_handshake_traffic_secret
+    );
+    assert_eq!(secrets.client_handshake_key, client_handshake_key);
+    assert_eq!(secrets.client_handshake_iv, client_handshake_iv);
+}
+
+/// Checks a KBKDF in counter mode with HMAC-SHA256 against the NIST CAVP
+/// SP 800-108 vectors (counter before the fixed input, 32-bit counter),
+/// COUNT=0.
+pub fn check_kbkdf_sp800_108<B>(mut builder: B)
+where
+    B: KbkdfBuilder,
+{
+    let key = [
+        0xdd, 0x1d, 0x91, 0xb7, 0xd9, 0x0b, 0x2b, 0xd3, 0x13, 0x85, 0x33, 0xce, 0x92, 0xb2, 0x72,
+        0xfb, 0xf8, 0xa3, 0x69, 0x31, 0x6a, 0xef, 0xe2, 0x42, 0xe6, 0x59, 0xcc, 0x0a, 0xe2, 0x38,
+        0xaf, 0xe0,
+    ];
+    let fixed_input = [
+        0x01, 0x32, 0x2b, 0x96, 0xb3, 0x0a, 0xcd, 0x19, 0x79, 0x79, 0x44, 0x4e, 0x46, 0x8e, 0x1c,
+        0x5c, 0x68, 0x59, 0xbf, 0x1b, 0x1c, 0xf9, 0x51, 0xb7, 0xe7, 0x25, 0x30, 0x3e, 0x23, 0x7e,
+        0x46, 0xb8, 0x64, 0xa1, 0x45, 0xfa, 0xb2, 0x5e, 0x51, 0x7b, 0x08, 0xf8, 0x68, 0x3d, 0x03,
+        0x15, 0xbb, 0x29, 0x11, 0xd8, 0x0a, 0x0e, 0x8a, 0xba, 0x17, 0xf3, 0xb4, 0x13, 0xfa, 0xac,
+    ];
+    let okm = [
+        0x10, 0x62, 0x13, 0x42, 0xbf, 0xb0, 0xfd, 0x40, 0x04, 0x6c, 0x0e, 0x29, 0xf2, 0xcf, 0xdb,
+        0xf0,
+    ];
+
+    let mut ctx = builder.for_derivation();
+    let mut out = [0u8; 16];
+    ctx.derive(&key, &fixed_input, &mut out);
+    assert_eq!(out, okm);
+}
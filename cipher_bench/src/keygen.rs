@@ -0,0 +1,133 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{output_directory, SignatureAlgorithm, SignatureBuilder};
+use criterion::measurement::{Measurement, ValueFormatter};
+use std::fs;
+
+/// Distribution of individually measured operations.
+///
+/// Criterion only reports the mean and median of batches, which hides
+/// the long tail of operations such as RSA key generation, whose running
+/// time depends on how many candidates are rejected before finding primes.
+pub struct Distribution {
+    sorted: Vec<f64>,
+}
+
+impl Distribution {
+    pub fn new(mut samples: Vec<f64>) -> Self {
+        samples.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        Self { sorted: samples }
+    }
+
+    /// Returns the nearest-rank percentile, for `p` between 0 and 100.
+    pub fn percentile(&self, p: f64) -> f64 {
+        if self.sorted.is_empty() {
+            return 0.0;
+        }
+        let rank = (p / 100.0 * self.sorted.len() as f64).ceil() as usize;
+        self.sorted[rank.clamp(1, self.sorted.len()) - 1]
+    }
+
+    pub fn max(&self) -> f64 {
+        self.sorted.last().copied().unwrap_or_default()
+    }
+
+    /// Formats the sample count, the median, p90, p99 and maximum in the
+    /// units of the measurement the samples were taken with.
+    pub fn format(&self, formatter: &dyn ValueFormatter) -> String {
+        format!(
+            "n={} p50={} p90={} p99={} max={}",
+            self.sorted.len(),
+            formatter.format_value(self.percentile(50.0)).trim(),
+            formatter.format_value(self.percentile(90.0)).trim(),
+            formatter.format_value(self.percentile(99.0)).trim(),
+            formatter.format_value(self.max()).trim()
+        )
+    }
+}
+
+/// Replaces the characters criterion replaces in directory names.
+fn directory_name(name: &str) -> String {
+    name.replace(
+        &['?', '"', '/', '\\', '*', '<', '>', ':', '|', '^'][..],
+        "_",
+    )
+}
+
+/// Benchmarks key pair generation. Every key pair criterion generates,
+/// warm-up included, is also measured on its own with `measurement`, which
+/// should be the group's, and their distribution is written to
+/// `distribution.txt` next to criterion's results for the benchmark.
+pub fn bench_keygen<B, M>(
+    group: &mut criterion::BenchmarkGroup<M>,
+    group_name: &str,
+    measurement: &M,
+    algorithm: SignatureAlgorithm,
+    mut builder: B,
+) where
+    B: SignatureBuilder,
+    M: Measurement,
+{
+    let mut samples = Vec::new();
+
+    group.bench_function(
+        criterion::BenchmarkId::new(algorithm.name(), "generate"),
+        |b| {
+            b.iter_custom(|iters| {
+                let mut total = measurement.zero();
+                for _ in 0..iters {
+                    let start = measurement.start();
+                    let ctx = builder.generate();
+                    let value = measurement.end(start);
+                    drop(ctx);
+                    samples.push(measurement.to_f64(&value));
+                    total = measurement.add(&total, &value);
+                }
+                total
+            });
+        },
+    );
+
+    // A filter may have skipped the benchmark, and cargo test --benches
+    // generates a single key pair.
+    if samples.len() < 2 {
+        return;
+    }
+
+    let dir = output_directory()
+        .join(directory_name(group_name))
+        .join(directory_name(algorithm.name()))
+        .join("generate");
+    let distribution = Distribution::new(samples);
+    let result = fs::create_dir_all(&dir).and_then(|_| {
+        fs::write(
+            dir.join("distribution.txt"),
+            distribution.format(measurement.formatter()) + "\n",
+        )
+    });
+    if let Err(e) = result {
+        panic!("cannot write to {}: {}", dir.display(), e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nearest_rank() {
+        let distribution = Distribution::new((1..=100).rev().map(f64::from).collect());
+        assert_eq!(distribution.percentile(50.0), 50.0);
+        assert_eq!(distribution.percentile(90.0), 90.0);
+        assert_eq!(distribution.percentile(99.0), 99.0);
+        assert_eq!(distribution.percentile(0.0), 1.0);
+        assert_eq!(distribution.percentile(100.0), 100.0);
+        assert_eq!(distribution.max(), 100.0);
+
+        let distribution = Distribution::new(vec![3.0, 1.0, 2.0]);
+        assert_eq!(distribution.percentile(50.0), 2.0);
+        assert_eq!(distribution.percentile(99.0), 3.0);
+
+        assert_eq!(Distribution::new(Vec::new()).percentile(50.0), 0.0);
+    }
+}
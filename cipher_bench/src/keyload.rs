@@ -0,0 +1,160 @@
+// SPDX-License-Identifier: Apache-2.0
+
+#[derive(Clone, Copy)]
+pub enum KeyAlgorithm {
+    Rsa2048,
+    EcdsaP256,
+    Ed25519,
+}
+
+impl KeyAlgorithm {
+    pub fn name(&self) -> &str {
+        match self {
+            KeyAlgorithm::Rsa2048 => "rsa-2048",
+            KeyAlgorithm::EcdsaP256 => "ecdsa-p256",
+            KeyAlgorithm::Ed25519 => "ed25519",
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub enum KeyFormat {
+    /// PKCS#1 for RSA and SEC1 for EC keys.
+    TypeSpecific,
+    Pkcs8,
+    /// PKCS#8 encrypted with PBES2, using PBKDF2-HMAC-SHA256 with 2048
+    /// iterations and AES-256-CBC.
+    EncryptedPkcs8,
+    SubjectPublicKeyInfo,
+}
+
+impl KeyFormat {
+    pub fn name(&self) -> &str {
+        match self {
+            KeyFormat::TypeSpecific => "type-specific",
+            KeyFormat::Pkcs8 => "pkcs8",
+            KeyFormat::EncryptedPkcs8 => "pkcs8-encrypted",
+            KeyFormat::SubjectPublicKeyInfo => "spki",
+        }
+    }
+
+    pub fn is_private(&self) -> bool {
+        !matches!(self, KeyFormat::SubjectPublicKeyInfo)
+    }
+}
+
+#[derive(Clone, Copy)]
+pub enum KeyEncoding {
+    Der,
+    Pem,
+}
+
+impl KeyEncoding {
+    pub fn name(&self) -> &str {
+        match self {
+            KeyEncoding::Der => "der",
+            KeyEncoding::Pem => "pem",
+        }
+    }
+}
+
+/// The passphrase of the encrypted PKCS#8 fixtures.
+pub const KEY_PASSPHRASE: &[u8] = b"cipher_bench";
+
+macro_rules! fixtures {
+    ($algorithm:expr, $format:expr, $encoding:expr, {
+        $($a:ident, $f:ident, $e:ident => $path:expr,)*
+    }) => {
+        match ($algorithm, $format, $encoding) {
+            $(
+                (KeyAlgorithm::$a, KeyFormat::$f, KeyEncoding::$e) => {
+                    Some(&include_bytes!(concat!("../keys/", $path))[..])
+                }
+            )*
+            _ => None,
+        }
+    };
+}
+
+/// Returns the fixture key in the given format and encoding, or `None`
+/// if the format does not exist for the algorithm.
+pub fn key_fixture(
+    algorithm: &KeyAlgorithm,
+    format: &KeyFormat,
+    encoding: &KeyEncoding,
+) -> Option<&'static [u8]> {
+    fixtures!(algorithm, format, encoding, {
+        Rsa2048, TypeSpecific, Der => "rsa-2048-pkcs1.der",
+        Rsa2048, TypeSpecific, Pem => "rsa-2048-pkcs1.pem",
+        Rsa2048, Pkcs8, Der => "rsa-2048-pkcs8.der",
+        Rsa2048, Pkcs8, Pem => "rsa-2048-pkcs8.pem",
+        Rsa2048, EncryptedPkcs8, Der => "rsa-2048-pkcs8-encrypted.der",
+        Rsa2048, EncryptedPkcs8, Pem => "rsa-2048-pkcs8-encrypted.pem",
+        Rsa2048, SubjectPublicKeyInfo, Der => "rsa-2048-spki.der",
+        Rsa2048, SubjectPublicKeyInfo, Pem => "rsa-2048-spki.pem",
+        EcdsaP256, TypeSpecific, Der => "ecdsa-p256-sec1.der",
+        EcdsaP256, TypeSpecific, Pem => "ecdsa-p256-sec1.pem",
+        EcdsaP256, Pkcs8, Der => "ecdsa-p256-pkcs8.der",
+        EcdsaP256, Pkcs8, Pem => "ecdsa-p256-pkcs8.pem",
+        EcdsaP256, EncryptedPkcs8, Der => "ecdsa-p256-pkcs8-encrypted.der",
+        EcdsaP256, EncryptedPkcs8, Pem => "ecdsa-p256-pkcs8-encrypted.pem",
+        EcdsaP256, SubjectPublicKeyInfo, Der => "ecdsa-p256-spki.der",
+        EcdsaP256, SubjectPublicKeyInfo, Pem => "ecdsa-p256-spki.pem",
+        Ed25519, Pkcs8, Der => "ed25519-pkcs8.der",
+        Ed25519, Pkcs8, Pem => "ed25519-pkcs8.pem",
+        Ed25519, EncryptedPkcs8, Der => "ed25519-pkcs8-encrypted.der",
+        Ed25519, EncryptedPkcs8, Pem => "ed25519-pkcs8-encrypted.pem",
+        Ed25519, SubjectPublicKeyInfo, Der => "ed25519-spki.der",
+        Ed25519, SubjectPublicKeyInfo, Pem => "ed25519-spki.pem",
+    })
+}
+
+pub trait KeyDecoder {
+    /// Decodes a key into the library's key object and releases it,
+    /// returning whether decoding succeeded.
+    fn decode(&mut self, encoded: &[u8]) -> bool;
+}
+
+pub trait KeyDecoderBuilder {
+    fn for_decoding(
+        &mut self,
+        algorithm: &KeyAlgorithm,
+        format: &KeyFormat,
+        encoding: &KeyEncoding,
+    ) -> Box<dyn KeyDecoder>;
+}
+
+/// Benchmarks decoding the fixture key, doing nothing if the format does
+/// not exist for the algorithm.
+pub fn bench_key_decoder<B, M>(
+    group: &mut criterion::BenchmarkGroup<M>,
+    algorithm: KeyAlgorithm,
+    format: KeyFormat,
+    encoding: KeyEncoding,
+    mut builder: B,
+) where
+    B: KeyDecoderBuilder,
+    M: criterion::measurement::Measurement,
+{
+    use criterion::black_box;
+
+    let encoded = match key_fixture(&algorithm, &format, &encoding) {
+        Some(encoded) => encoded,
+        None => return,
+    };
+
+    let mut ctx = builder.for_decoding(&algorithm, &format, &encoding);
+
+    group.bench_with_input(
+        criterion::BenchmarkId::new(
+            algorithm.name(),
+            format!("{}-{}", format.name(), encoding.name()),
+        ),
+        encoded,
+        |b, param| {
+            b.iter(|| {
+                assert!(ctx.decode(black_box(param)));
+            });
+        },
+    );
+}
@@ -1,10 +1,58 @@
 // SPDX-License-Identifier: Apache-2.0
 
+mod sweep;
+pub use sweep::*;
+
 mod block;
 pub use block::*;
 
 mod aead;
 pub use aead::*;
 
-pub const STEP: usize = 1024;
-pub const ITER: usize = 8;
+mod mac;
+pub use mac::*;
+
+mod uhash;
+pub use uhash::*;
+
+mod kdf;
+pub use kdf::*;
+
+mod password;
+pub use password::*;
+
+mod pubkey;
+pub use pubkey::*;
+
+mod hpke;
+pub use hpke::*;
+
+mod keygen;
+pub use keygen::*;
+
+mod bignum;
+pub use bignum::*;
+
+mod ecc;
+pub use ecc::*;
+
+mod rng;
+pub use rng::*;
+
+mod keyload;
+pub use keyload::*;
+
+mod cputime;
+pub use cputime::*;
+
+mod cycles;
+pub use cycles::*;
+
+mod perf;
+pub use perf::*;
+
+mod measurement;
+pub use measurement::*;
+
+mod callgrind;
+pub use callgrind::*;
@@ -0,0 +1,336 @@
+// SPDX-License-Identifier: Apache-2.0
+
+pub enum MacAlgorithm {
+    Aes128Cmac,
+    Aes128Gmac,
+    HmacSha256,
+    Poly1305,
+    Poly1305Aes,
+    Umac32,
+    Umac64,
+    Umac96,
+    Umac128,
+}
+
+impl MacAlgorithm {
+    pub fn name(&self) -> &str {
+        match self {
+            MacAlgorithm::Aes128Cmac => "aes-128-cmac",
+            MacAlgorithm::Aes128Gmac => "aes-128-gmac",
+            MacAlgorithm::HmacSha256 => "hmac-sha256",
+            MacAlgorithm::Poly1305 => "poly1305",
+            MacAlgorithm::Poly1305Aes => "poly1305-aes",
+            MacAlgorithm::Umac32 => "umac-32",
+            MacAlgorithm::Umac64 => "umac-64",
+            MacAlgorithm::Umac96 => "umac-96",
+            MacAlgorithm::Umac128 => "umac-128",
+        }
+    }
+
+    pub fn key_len(&self) -> usize {
+        match self {
+            MacAlgorithm::Aes128Cmac => 16,
+            MacAlgorithm::Aes128Gmac => 16,
+            MacAlgorithm::HmacSha256 => 32,
+            MacAlgorithm::Poly1305 => 32,
+            MacAlgorithm::Poly1305Aes => 32,
+            MacAlgorithm::Umac32 => 16,
+            MacAlgorithm::Umac64 => 16,
+            MacAlgorithm::Umac96 => 16,
+            MacAlgorithm::Umac128 => 16,
+        }
+    }
+
+    pub fn nonce_len(&self) -> usize {
+        match self {
+            MacAlgorithm::Aes128Cmac => 0,
+            MacAlgorithm::Aes128Gmac => 12,
+            MacAlgorithm::HmacSha256 => 0,
+            MacAlgorithm::Poly1305 => 0,
+            MacAlgorithm::Poly1305Aes => 16,
+            MacAlgorithm::Umac32 => 16,
+            MacAlgorithm::Umac64 => 16,
+            MacAlgorithm::Umac96 => 16,
+            MacAlgorithm::Umac128 => 16,
+        }
+    }
+
+    pub fn digest_len(&self) -> usize {
+        match self {
+            MacAlgorithm::Aes128Cmac => 16,
+            MacAlgorithm::Aes128Gmac => 16,
+            MacAlgorithm::HmacSha256 => 32,
+            MacAlgorithm::Poly1305 => 16,
+            MacAlgorithm::Poly1305Aes => 16,
+            MacAlgorithm::Umac32 => 4,
+            MacAlgorithm::Umac64 => 8,
+            MacAlgorithm::Umac96 => 12,
+            MacAlgorithm::Umac128 => 16,
+        }
+    }
+}
+
+pub trait Mac {
+    fn update(&mut self, data: &[u8]);
+    /// Writes the tag of the message and starts the next one, under the
+    /// next nonce for MACs that take one.
+    fn digest(&mut self, digest: &mut [u8]);
+}
+
+pub trait MacBuilder {
+    fn nonce(&mut self, nonce: &[u8]) -> &mut Self;
+    fn for_key(&mut self, key: &[u8]) -> Box<dyn Mac>;
+}
+
+/// Increments `nonce` as a big-endian counter, for MACs that need a
+/// fresh nonce for every message.
+pub fn increment_nonce(nonce: &mut [u8]) {
+    for byte in nonce.iter_mut().rev() {
+        *byte = byte.wrapping_add(1);
+        if *byte != 0 {
+            break;
+        }
+    }
+}
+
+/// Benchmarks authenticating `len` byte messages with a single key, each
+/// one fed in a single update and finished with its tag.
+pub fn bench_mac<B, M>(
+    group: &mut criterion::BenchmarkGroup<M>,
+    algorithm: MacAlgorithm,
+    builder: B,
+    len: usize,
+) where
+    B: MacBuilder,
+    M: criterion::measurement::Measurement,
+{
+    bench_mac_input(
+        group,
+        algorithm.name(),
+        algorithm.key_len(),
+        algorithm.nonce_len(),
+        Some(algorithm.digest_len()),
+        builder,
+        len,
+    );
+}
+
+/// Feeds `len` bytes per iteration to a context keyed with a random key
+/// and nonce, computing a `digest_len` byte tag after each update if
+/// given.
+pub(crate) fn bench_mac_input<B, M>(
+    group: &mut criterion::BenchmarkGroup<M>,
+    name: &str,
+    key_len: usize,
+    nonce_len: usize,
+    digest_len: Option<usize>,
+    mut builder: B,
+    len: usize,
+) where
+    B: MacBuilder,
+    M: criterion::measurement::Measurement,
+{
+    group.bench_with_input(criterion::BenchmarkId::new(name, len), &len, |b, param| {
+        use criterion::black_box;
+        use rand::prelude::*;
+
+        let mut rng = rand::thread_rng();
+
+        let mut key_bytes = vec![0u8; key_len];
+        rng.fill(key_bytes.as_mut_slice());
+
+        let mut nonce_bytes = vec![0u8; nonce_len];
+        rng.fill(nonce_bytes.as_mut_slice());
+
+        let mut ctx = builder.nonce(&nonce_bytes).for_key(&key_bytes);
+
+        let dbuf = vec![0u8; *param];
+
+        match digest_len {
+            Some(digest_len) => {
+                let mut digest = vec![0u8; digest_len];
+                b.iter(|| {
+                    ctx.update(black_box(&dbuf));
+                    ctx.digest(black_box(&mut digest));
+                });
+            }
+            None => b.iter(|| ctx.update(black_box(&dbuf))),
+        }
+    });
+}
+
+struct MacVector {
+    key: &'static [u8],
+    nonce: &'static [u8],
+    message: &'static [u8],
+    tag: &'static [u8],
+}
+
+fn mac_vector(algorithm: &MacAlgorithm) -> MacVector {
+    match algorithm {
+        // RFC 4493, section 4, example 2.
+        MacAlgorithm::Aes128Cmac => MacVector {
+            key: &[
+                0x2b, 0x7e, 0x15, 0x16, 0x28, 0xae, 0xd2, 0xa6, 0xab, 0xf7, 0x15, 0x88, 0x09, 0xcf,
+                0x4f, 0x3c,
+            ],
+            nonce: &[],
+            message: &[
+                0x6b, 0xc1, 0xbe, 0xe2, 0x2e, 0x40, 0x9f, 0x96, 0xe9, 0x3d, 0x7e, 0x11, 0x73, 0x93,
+                0x17, 0x2a,
+            ],
+            tag: &[
+                0x07, 0x0a, 0x16, 0xb4, 0x6b, 0x4d, 0x41, 0x44, 0xf7, 0x9b, 0xdd, 0x9d, 0xd0, 0x4a,
+                0x28, 0x7c,
+            ],
+        },
+        // NIST GCM test vectors (gcmEncryptExtIV128), PTlen = 0, AADlen = 128, count 0.
+        MacAlgorithm::Aes128Gmac => MacVector {
+            key: &[
+                0x77, 0xbe, 0x63, 0x70, 0x89, 0x71, 0xc4, 0xe2, 0x40, 0xd1, 0xcb, 0x79, 0xe8, 0xd7,
+                0x7f, 0xeb,
+            ],
+            nonce: &[
+                0xe0, 0xe0, 0x0f, 0x19, 0xfe, 0xd7, 0xba, 0x01, 0x36, 0xa7, 0x97, 0xf3,
+            ],
+            message: &[
+                0x7a, 0x43, 0xec, 0x1d, 0x9c, 0x0a, 0x5a, 0x78, 0xa0, 0xb1, 0x65, 0x33, 0xa6, 0x21,
+                0x3c, 0xab,
+            ],
+            tag: &[
+                0x20, 0x9f, 0xcc, 0x8d, 0x36, 0x75, 0xed, 0x93, 0x8e, 0x9c, 0x71, 0x66, 0x70, 0x9d,
+                0xd9, 0x46,
+            ],
+        },
+        // RFC 4231, test case 2.
+        MacAlgorithm::HmacSha256 => MacVector {
+            key: &[0x4a, 0x65, 0x66, 0x65],
+            nonce: &[],
+            message: &[
+                0x77, 0x68, 0x61, 0x74, 0x20, 0x64, 0x6f, 0x20, 0x79, 0x61, 0x20, 0x77, 0x61, 0x6e,
+                0x74, 0x20, 0x66, 0x6f, 0x72, 0x20, 0x6e, 0x6f, 0x74, 0x68, 0x69, 0x6e, 0x67, 0x3f,
+            ],
+            tag: &[
+                0x5b, 0xdc, 0xc1, 0x46, 0xbf, 0x60, 0x75, 0x4e, 0x6a, 0x04, 0x24, 0x26, 0x08, 0x95,
+                0x75, 0xc7, 0x5a, 0x00, 0x3f, 0x08, 0x9d, 0x27, 0x39, 0x83, 0x9d, 0xec, 0x58, 0xb9,
+                0x64, 0xec, 0x38, 0x43,
+            ],
+        },
+        // RFC 8439, section 2.5.2.
+        MacAlgorithm::Poly1305 => MacVector {
+            key: &[
+                0x85, 0xd6, 0xbe, 0x78, 0x57, 0x55, 0x6d, 0x33, 0x7f, 0x44, 0x52, 0xfe, 0x42, 0xd5,
+                0x06, 0xa8, 0x01, 0x03, 0x80, 0x8a, 0xfb, 0x0d, 0xb2, 0xfd, 0x4a, 0xbf, 0xf6, 0xaf,
+                0x41, 0x49, 0xf5, 0x1b,
+            ],
+            nonce: &[],
+            message: &[
+                0x43, 0x72, 0x79, 0x70, 0x74, 0x6f, 0x67, 0x72, 0x61, 0x70, 0x68, 0x69, 0x63, 0x20,
+                0x46, 0x6f, 0x72, 0x75, 0x6d, 0x20, 0x52, 0x65, 0x73, 0x65, 0x61, 0x72, 0x63, 0x68,
+                0x20, 0x47, 0x72, 0x6f, 0x75, 0x70,
+            ],
+            tag: &[
+                0xa8, 0x06, 0x1d, 0xc1, 0x30, 0x51, 0x36, 0xc6, 0xc2, 0x2b, 0x8b, 0xaf, 0x0c, 0x01,
+                0x27, 0xa9,
+            ],
+        },
+        // The Poly1305-AES paper, appendix B, with the key given as the
+        // AES key followed by r, as nettle takes it.
+        MacAlgorithm::Poly1305Aes => MacVector {
+            key: &[
+                0xec, 0x07, 0x4c, 0x83, 0x55, 0x80, 0x74, 0x17, 0x01, 0x42, 0x5b, 0x62, 0x32, 0x35,
+                0xad, 0xd6, 0x85, 0x1f, 0xc4, 0x0c, 0x34, 0x67, 0xac, 0x0b, 0xe0, 0x5c, 0xc2, 0x04,
+                0x04, 0xf3, 0xf7, 0x00,
+            ],
+            nonce: &[
+                0xfb, 0x44, 0x73, 0x50, 0xc4, 0xe8, 0x68, 0xc5, 0x2a, 0xc3, 0x27, 0x5c, 0xf9, 0xd4,
+                0x32, 0x7e,
+            ],
+            message: &[0xf3, 0xf6],
+            tag: &[
+                0xf4, 0xc6, 0x33, 0xc3, 0x04, 0x4f, 0xc1, 0x45, 0xf8, 0x4f, 0x33, 0x5c, 0xb8, 0x19,
+                0x53, 0xde,
+            ],
+        },
+        // RFC 4418, appendix, 'a' * 3.
+        MacAlgorithm::Umac32 => MacVector {
+            key: &[
+                0x61, 0x62, 0x63, 0x64, 0x65, 0x66, 0x67, 0x68, 0x69, 0x6a, 0x6b, 0x6c, 0x6d, 0x6e,
+                0x6f, 0x70,
+            ],
+            nonce: &[0x62, 0x63, 0x64, 0x65, 0x66, 0x67, 0x68, 0x69],
+            message: &[0x61, 0x61, 0x61],
+            tag: &[0x3b, 0x91, 0xd1, 0x02],
+        },
+        // RFC 4418, appendix, 'a' * 3.
+        MacAlgorithm::Umac64 => MacVector {
+            key: &[
+                0x61, 0x62, 0x63, 0x64, 0x65, 0x66, 0x67, 0x68, 0x69, 0x6a, 0x6b, 0x6c, 0x6d, 0x6e,
+                0x6f, 0x70,
+            ],
+            nonce: &[0x62, 0x63, 0x64, 0x65, 0x66, 0x67, 0x68, 0x69],
+            message: &[0x61, 0x61, 0x61],
+            tag: &[0x44, 0xb5, 0xcb, 0x54, 0x2f, 0x22, 0x01, 0x04],
+        },
+        // RFC 4418, appendix, 'a' * 3.
+        MacAlgorithm::Umac96 => MacVector {
+            key: &[
+                0x61, 0x62, 0x63, 0x64, 0x65, 0x66, 0x67, 0x68, 0x69, 0x6a, 0x6b, 0x6c, 0x6d, 0x6e,
+                0x6f, 0x70,
+            ],
+            nonce: &[0x62, 0x63, 0x64, 0x65, 0x66, 0x67, 0x68, 0x69],
+            message: &[0x61, 0x61, 0x61],
+            tag: &[
+                0x18, 0x5e, 0x4f, 0xe9, 0x05, 0xcb, 0xa7, 0xbd, 0x85, 0xe4, 0xc2, 0xdc,
+            ],
+        },
+        // RFC 4418, appendix, 'a' * 3.
+        MacAlgorithm::Umac128 => MacVector {
+            key: &[
+                0x61, 0x62, 0x63, 0x64, 0x65, 0x66, 0x67, 0x68, 0x69, 0x6a, 0x6b, 0x6c, 0x6d, 0x6e,
+                0x6f, 0x70,
+            ],
+            nonce: &[0x62, 0x63, 0x64, 0x65, 0x66, 0x67, 0x68, 0x69],
+            message: &[0x61, 0x61, 0x61],
+            tag: &[
+                0x18, 0x5e, 0x4f, 0xe9, 0x05, 0xcb, 0xa7, 0xbd, 0x85, 0xe4, 0xc2, 0xdc, 0x3d, 0x11,
+                0x7d, 0x8d,
+            ],
+        },
+    }
+}
+
+/// Checks a MAC implementation against a published test vector. MACs
+/// without a nonce must give the same tag again for the next message.
+pub fn check_mac<B>(algorithm: MacAlgorithm, mut builder: B)
+where
+    B: MacBuilder,
+{
+    let vector = mac_vector(&algorithm);
+
+    let mut ctx = builder.nonce(vector.nonce).for_key(vector.key);
+    let mut digest = vec![0u8; algorithm.digest_len()];
+    ctx.update(vector.message);
+    ctx.digest(&mut digest);
+    assert_eq!(digest, vector.tag, "{}", algorithm.name());
+
+    if algorithm.nonce_len() == 0 {
+        ctx.update(vector.message);
+        ctx.digest(&mut digest);
+        assert_eq!(digest, vector.tag, "{} after digest", algorithm.name());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn increment() {
+        let mut nonce = [0x00, 0xff, 0xff];
+        increment_nonce(&mut nonce);
+        assert_eq!(nonce, [0x01, 0x00, 0x00]);
+        increment_nonce(&mut nonce);
+        assert_eq!(nonce, [0x01, 0x00, 0x01]);
+    }
+}
@@ -0,0 +1,196 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cputime::CpuTime;
+use crate::cycles::{Cycles, CyclesFormatter};
+use crate::perf::{PerfCounter, PerfEvent, PerfReading};
+use criterion::measurement::{Measurement, ValueFormatter, WallTime};
+use criterion::Criterion;
+use std::env;
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+/// What the benchmarks measure, chosen with the `CIPHER_BENCH_MEASUREMENT`
+/// environment variable.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MeasurementKind {
+    /// `wall-time`, criterion's default.
+    WallTime,
+    /// `cycles`, see [`Cycles`].
+    Cycles,
+    /// `cpu-time`, see [`CpuTime`].
+    CpuTime,
+    /// A [`PerfEvent`] by its name, for example `instructions`, see
+    /// [`PerfCounter`].
+    Perf(PerfEvent),
+}
+
+impl MeasurementKind {
+    pub fn name(&self) -> &str {
+        match self {
+            MeasurementKind::WallTime => "wall-time",
+            MeasurementKind::Cycles => "cycles",
+            MeasurementKind::CpuTime => "cpu-time",
+            MeasurementKind::Perf(event) => event.name(),
+        }
+    }
+
+    /// Reads `CIPHER_BENCH_MEASUREMENT`, defaulting to wall time. Panics on
+    /// an unknown name rather than silently measuring something else.
+    pub fn from_env() -> Self {
+        let name = match env::var("CIPHER_BENCH_MEASUREMENT") {
+            Ok(name) => name,
+            Err(_) => return MeasurementKind::WallTime,
+        };
+        [
+            MeasurementKind::WallTime,
+            MeasurementKind::Cycles,
+            MeasurementKind::CpuTime,
+        ]
+        .iter()
+        .copied()
+        .chain(PerfEvent::ALL.iter().copied().map(MeasurementKind::Perf))
+        .find(|kind| kind.name() == name)
+        .unwrap_or_else(|| panic!("unknown measurement `{}`", name))
+    }
+}
+
+/// A measurement selected at run time, so that the benches are compiled
+/// once for all of them. Values are kept as `f64` in the unit of the
+/// selected measurement. Clones share the same counter.
+#[derive(Clone)]
+pub struct SelectedMeasurement {
+    kind: MeasurementKind,
+    perf: Option<Rc<PerfCounter>>,
+}
+
+thread_local! {
+    static SELECTED: SelectedMeasurement = SelectedMeasurement::new(MeasurementKind::from_env());
+}
+
+impl SelectedMeasurement {
+    /// Falls back to wall time, with a notice, when a perf counter cannot
+    /// be opened.
+    pub fn new(kind: MeasurementKind) -> Self {
+        if let MeasurementKind::Perf(event) = kind {
+            match PerfCounter::open(event) {
+                Ok(perf) => {
+                    return Self {
+                        kind,
+                        perf: Some(Rc::new(perf)),
+                    }
+                }
+                Err(e) => eprintln!(
+                    "{} unavailable, measuring wall time instead: {}",
+                    event.name(),
+                    e
+                ),
+            }
+            return Self::new(MeasurementKind::WallTime);
+        }
+        Self { kind, perf: None }
+    }
+
+    /// Returns the measurement `CIPHER_BENCH_MEASUREMENT` selects. It is
+    /// opened on the first call and shared by the later ones, so that code
+    /// measuring on its own, such as [`bench_keygen`](crate::bench_keygen),
+    /// counts with the same counter as criterion.
+    pub fn from_env() -> Self {
+        SELECTED.with(|measurement| measurement.clone())
+    }
+
+    fn perf(&self) -> &PerfCounter {
+        self.perf.as_ref().unwrap()
+    }
+
+    pub fn kind(&self) -> MeasurementKind {
+        self.kind
+    }
+}
+
+pub enum SelectedIntermediate {
+    WallTime(Instant),
+    Cycles(u64),
+    CpuTime(Duration),
+    Perf(Option<PerfReading>),
+}
+
+impl Measurement for SelectedMeasurement {
+    type Intermediate = SelectedIntermediate;
+    type Value = f64;
+
+    fn start(&self) -> Self::Intermediate {
+        match self.kind {
+            MeasurementKind::WallTime => SelectedIntermediate::WallTime(WallTime.start()),
+            MeasurementKind::Cycles => SelectedIntermediate::Cycles(Cycles.start()),
+            MeasurementKind::CpuTime => SelectedIntermediate::CpuTime(CpuTime.start()),
+            MeasurementKind::Perf(_) => SelectedIntermediate::Perf(self.perf().start()),
+        }
+    }
+
+    fn end(&self, i: Self::Intermediate) -> Self::Value {
+        match i {
+            SelectedIntermediate::WallTime(i) => WallTime.to_f64(&WallTime.end(i)),
+            SelectedIntermediate::Cycles(i) => Cycles.to_f64(&Cycles.end(i)),
+            SelectedIntermediate::CpuTime(i) => CpuTime.to_f64(&CpuTime.end(i)),
+            SelectedIntermediate::Perf(i) => self.perf().to_f64(&self.perf().end(i)),
+        }
+    }
+
+    fn add(&self, v1: &Self::Value, v2: &Self::Value) -> Self::Value {
+        v1 + v2
+    }
+
+    fn zero(&self) -> Self::Value {
+        0.0
+    }
+
+    fn to_f64(&self, value: &Self::Value) -> f64 {
+        *value
+    }
+
+    fn formatter(&self) -> &dyn ValueFormatter {
+        static WALL_TIME: WallTime = WallTime;
+
+        match self.kind {
+            MeasurementKind::WallTime => WALL_TIME.formatter(),
+            MeasurementKind::Cycles => &CyclesFormatter,
+            MeasurementKind::CpuTime => CpuTime.formatter(),
+            MeasurementKind::Perf(_) => self.perf().formatter(),
+        }
+    }
+}
+
+/// Returns the directory criterion writes its results to: `CRITERION_HOME`,
+/// or the `criterion` directory of the cargo target directory the running
+/// bench was built in.
+pub fn output_directory() -> PathBuf {
+    if let Some(dir) = env::var_os("CRITERION_HOME") {
+        return PathBuf::from(dir);
+    }
+    if let Some(dir) = env::var_os("CARGO_TARGET_DIR") {
+        return PathBuf::from(dir).join("criterion");
+    }
+    // Benches run from <target>/<profile>/deps.
+    env::current_exe()
+        .ok()
+        .and_then(|exe| Some(exe.parent()?.parent()?.parent()?.join("criterion")))
+        .unwrap_or_else(|| PathBuf::from("target/criterion"))
+}
+
+/// Returns the criterion configuration for the benches, measuring what
+/// `CIPHER_BENCH_MEASUREMENT` selects and writing to [`output_directory`].
+pub fn criterion() -> Criterion<SelectedMeasurement> {
+    let measurement = SelectedMeasurement::from_env();
+    let kind = measurement.kind();
+    let criterion = Criterion::default()
+        .with_measurement(measurement)
+        .output_directory(&output_directory());
+
+    // Results of different measurements cannot be compared, so every
+    // measurement but wall time keeps a baseline of its own.
+    match kind {
+        MeasurementKind::WallTime => criterion,
+        _ => criterion.save_baseline(kind.name().to_string()),
+    }
+}
@@ -0,0 +1,102 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::fmt;
+
+pub enum PasswordHashAlgorithm {
+    Pbkdf2Sha256,
+    Pbkdf2Sha512,
+    Scrypt,
+    Argon2id,
+    BalloonSha256,
+}
+
+impl PasswordHashAlgorithm {
+    pub fn name(&self) -> &str {
+        match self {
+            PasswordHashAlgorithm::Pbkdf2Sha256 => "pbkdf2-hmac-sha256",
+            PasswordHashAlgorithm::Pbkdf2Sha512 => "pbkdf2-hmac-sha512",
+            PasswordHashAlgorithm::Scrypt => "scrypt",
+            PasswordHashAlgorithm::Argon2id => "argon2id",
+            PasswordHashAlgorithm::BalloonSha256 => "balloon-sha256",
+        }
+    }
+
+    pub fn salt_len(&self) -> usize {
+        16
+    }
+
+    pub fn output_len(&self) -> usize {
+        32
+    }
+}
+
+/// Cost parameters of a password hash.
+///
+/// The meaning of each field depends on the algorithm:
+///
+/// | algorithm | `time`     | `memory`         | `parallelism` |
+/// |-----------|------------|------------------|---------------|
+/// | PBKDF2    | iterations | unused           | unused        |
+/// | scrypt    | unused     | N                | p (r is 8)    |
+/// | Argon2id  | passes     | memory in KiB    | lanes         |
+/// | balloon   | rounds     | blocks           | unused        |
+#[derive(Clone, Copy, Debug)]
+pub struct PasswordHashCost {
+    pub time: u32,
+    pub memory: u32,
+    pub parallelism: u32,
+}
+
+impl fmt::Display for PasswordHashCost {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "t{}-m{}-p{}", self.time, self.memory, self.parallelism)
+    }
+}
+
+pub trait PasswordHash {
+    fn derive(&mut self, password: &[u8], salt: &[u8], key: &mut [u8]);
+}
+
+pub trait PasswordHashBuilder {
+    fn cost(&mut self, cost: &PasswordHashCost) -> &mut Self;
+    fn for_derivation(&mut self) -> Box<dyn PasswordHash>;
+}
+
+pub fn bench_password_hash<B, M>(
+    group: &mut criterion::BenchmarkGroup<M>,
+    algorithm: PasswordHashAlgorithm,
+    mut builder: B,
+    cost: PasswordHashCost,
+) where
+    B: PasswordHashBuilder,
+    M: criterion::measurement::Measurement,
+{
+    group.bench_with_input(
+        criterion::BenchmarkId::new(algorithm.name(), cost),
+        &cost,
+        |b, param| {
+            use criterion::black_box;
+            use rand::prelude::*;
+
+            let mut rng = rand::thread_rng();
+
+            let mut password_bytes = vec![0u8; 16];
+            rng.fill(password_bytes.as_mut_slice());
+
+            let mut salt_bytes = vec![0u8; algorithm.salt_len()];
+            rng.fill(salt_bytes.as_mut_slice());
+
+            let mut ctx = builder.cost(param).for_derivation();
+
+            let mut key = vec![0u8; algorithm.output_len()];
+
+            b.iter(|| {
+                ctx.derive(
+                    black_box(&password_bytes),
+                    black_box(&salt_bytes),
+                    black_box(&mut key),
+                );
+            });
+        },
+    );
+}
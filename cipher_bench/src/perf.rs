@@ -0,0 +1,380 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use criterion::measurement::{Measurement, ValueFormatter};
+use criterion::Throughput;
+use std::cell::{Cell, RefCell};
+use std::io;
+use std::mem;
+use std::os::unix::io::RawFd;
+
+/// A hardware event counted with `perf_event_open`, named as in
+/// `perf stat -e`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PerfEvent {
+    Instructions,
+    CpuCycles,
+    BranchMisses,
+    L1dMisses,
+}
+
+const PERF_TYPE_HARDWARE: u32 = 0;
+const PERF_TYPE_HW_CACHE: u32 = 3;
+
+const PERF_COUNT_HW_CPU_CYCLES: u64 = 0;
+const PERF_COUNT_HW_INSTRUCTIONS: u64 = 1;
+const PERF_COUNT_HW_BRANCH_MISSES: u64 = 5;
+
+const PERF_COUNT_HW_CACHE_L1D: u64 = 0;
+const PERF_COUNT_HW_CACHE_OP_READ: u64 = 0;
+const PERF_COUNT_HW_CACHE_RESULT_MISS: u64 = 1;
+
+const PERF_FLAG_FD_CLOEXEC: libc::c_ulong = 8;
+
+impl PerfEvent {
+    pub const ALL: [PerfEvent; 4] = [
+        PerfEvent::Instructions,
+        PerfEvent::CpuCycles,
+        PerfEvent::BranchMisses,
+        PerfEvent::L1dMisses,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            PerfEvent::Instructions => "instructions",
+            PerfEvent::CpuCycles => "cpu-cycles",
+            PerfEvent::BranchMisses => "branch-misses",
+            PerfEvent::L1dMisses => "L1-dcache-load-misses",
+        }
+    }
+
+    fn type_config(&self) -> (u32, u64) {
+        match self {
+            PerfEvent::Instructions => (PERF_TYPE_HARDWARE, PERF_COUNT_HW_INSTRUCTIONS),
+            PerfEvent::CpuCycles => (PERF_TYPE_HARDWARE, PERF_COUNT_HW_CPU_CYCLES),
+            PerfEvent::BranchMisses => (PERF_TYPE_HARDWARE, PERF_COUNT_HW_BRANCH_MISSES),
+            PerfEvent::L1dMisses => (
+                PERF_TYPE_HW_CACHE,
+                PERF_COUNT_HW_CACHE_L1D
+                    | PERF_COUNT_HW_CACHE_OP_READ << 8
+                    | PERF_COUNT_HW_CACHE_RESULT_MISS << 16,
+            ),
+        }
+    }
+
+    /// The units of a count, of a thousand and a million counts, and of
+    /// counts per byte and per element.
+    fn units(&self) -> [&'static str; 5] {
+        match self {
+            PerfEvent::Instructions => ["insns", "Kinsns", "Minsns", "insns/B", "insns/elem"],
+            PerfEvent::CpuCycles => ["cycles", "Kcycles", "Mcycles", "cycles/B", "cycles/elem"],
+            PerfEvent::BranchMisses => [
+                "br-misses",
+                "Kbr-misses",
+                "Mbr-misses",
+                "br-misses/B",
+                "br-misses/elem",
+            ],
+            PerfEvent::L1dMisses => [
+                "L1d-misses",
+                "KL1d-misses",
+                "ML1d-misses",
+                "L1d-misses/B",
+                "L1d-misses/elem",
+            ],
+        }
+    }
+}
+
+/// The leading, `PERF_ATTR_SIZE_VER0`, part of `struct perf_event_attr`,
+/// which is all the kernel needs for counting.
+#[repr(C)]
+#[derive(Default)]
+struct PerfEventAttr {
+    type_: u32,
+    size: u32,
+    config: u64,
+    sample_period: u64,
+    sample_type: u64,
+    read_format: u64,
+    flags: u64,
+    wakeup_events: u32,
+    bp_type: u32,
+    config1: u64,
+}
+
+const ATTR_FLAG_EXCLUDE_KERNEL: u64 = 1 << 5;
+const ATTR_FLAG_EXCLUDE_HV: u64 = 1 << 6;
+
+const PERF_FORMAT_TOTAL_TIME_ENABLED: u64 = 1 << 0;
+const PERF_FORMAT_TOTAL_TIME_RUNNING: u64 = 1 << 1;
+const PERF_FORMAT_GROUP: u64 = 1 << 3;
+
+const MAX_EVENTS: usize = PerfEvent::ALL.len();
+
+/// A read of all the counters of a group, at the start of a measurement.
+#[derive(Clone, Copy, Debug)]
+pub struct PerfReading {
+    enabled: u64,
+    running: u64,
+    counts: [u64; MAX_EVENTS],
+}
+
+/// Counts [`PerfEvent::ALL`] in user space for the calling thread, which
+/// is the thread criterion runs the benchmarks on, as one group that the
+/// kernel schedules together. The group leader is the event criterion
+/// measures; the others are added to totals which are reported per
+/// leader event with each benchmark's results. Opening the leader fails
+/// where the kernel does not support the event or `perf_event_paranoid`
+/// and container policies forbid it, and an event that cannot be added
+/// to the group is left out with a notice.
+///
+/// When more events are counted than the PMU has counters, the kernel
+/// multiplexes the group, and counts are scaled up by the time it was
+/// enabled over the time it was actually counted.
+pub struct PerfCounter {
+    /// The leader, then the other events in the order they are read.
+    events: Vec<PerfEvent>,
+    fds: Vec<RawFd>,
+    totals: RefCell<[u64; MAX_EVENTS]>,
+    unscheduled: Cell<bool>,
+}
+
+fn perf_event_open(event: PerfEvent, group_fd: RawFd) -> io::Result<RawFd> {
+    let (type_, config) = event.type_config();
+    let attr = PerfEventAttr {
+        type_,
+        size: mem::size_of::<PerfEventAttr>() as u32,
+        config,
+        // Leaving out the kernel is also what unprivileged users are
+        // allowed to count.
+        flags: ATTR_FLAG_EXCLUDE_KERNEL | ATTR_FLAG_EXCLUDE_HV,
+        read_format: PERF_FORMAT_GROUP
+            | PERF_FORMAT_TOTAL_TIME_ENABLED
+            | PERF_FORMAT_TOTAL_TIME_RUNNING,
+        ..Default::default()
+    };
+    let fd = unsafe {
+        libc::syscall(
+            libc::SYS_perf_event_open,
+            &attr as *const PerfEventAttr,
+            0 as libc::pid_t,
+            -1 as libc::c_int,
+            group_fd as libc::c_int,
+            PERF_FLAG_FD_CLOEXEC,
+        )
+    };
+    if fd < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(fd as RawFd)
+}
+
+impl PerfCounter {
+    /// Opens a group led by `event`.
+    pub fn open(event: PerfEvent) -> io::Result<Self> {
+        let mut counter = Self {
+            events: vec![event],
+            fds: vec![perf_event_open(event, -1)?],
+            totals: RefCell::new([0; MAX_EVENTS]),
+            unscheduled: Cell::new(false),
+        };
+        for &member in PerfEvent::ALL.iter().filter(|&&member| member != event) {
+            match perf_event_open(member, counter.fds[0]) {
+                Ok(fd) => {
+                    counter.events.push(member);
+                    counter.fds.push(fd);
+                }
+                Err(e) => eprintln!("{} unavailable: {}", member.name(), e),
+            }
+        }
+        Ok(counter)
+    }
+
+    pub fn event(&self) -> PerfEvent {
+        self.events[0]
+    }
+
+    /// Reads the counts of the group, and the times it was enabled and
+    /// running, or `None` if the read fails.
+    fn read(&self) -> Option<PerfReading> {
+        // nr, time_enabled, time_running, then a value per event.
+        let mut values = [0u64; 3 + MAX_EVENTS];
+        let len = (3 + self.events.len()) * mem::size_of::<u64>();
+        let ret = unsafe { libc::read(self.fds[0], values.as_mut_ptr() as *mut libc::c_void, len) };
+        if ret != len as isize {
+            return None;
+        }
+        let mut counts = [0; MAX_EVENTS];
+        counts.copy_from_slice(&values[3..]);
+        Some(PerfReading {
+            enabled: values[1],
+            running: values[2],
+            counts,
+        })
+    }
+
+    /// Prints the totals of the other events per leader event, and starts
+    /// new totals.
+    fn report(&self) {
+        let totals = self.totals.replace([0; MAX_EVENTS]);
+        if totals[0] == 0 {
+            return;
+        }
+        let ratios: Vec<String> = self.events[1..]
+            .iter()
+            .zip(&totals[1..])
+            .map(|(event, &total)| {
+                format!("{:.4} {}", total as f64 / totals[0] as f64, event.name())
+            })
+            .collect();
+        if !ratios.is_empty() {
+            println!(
+                "{:24}per {}: {}",
+                "",
+                self.event().name(),
+                ratios.join(", ")
+            );
+        }
+    }
+}
+
+/// Extrapolates a count to the whole time the event was enabled, or
+/// returns `None` if it was never actually counted.
+fn scale(count: u64, enabled: u64, running: u64) -> Option<u64> {
+    if running == enabled {
+        Some(count)
+    } else if running == 0 {
+        None
+    } else {
+        Some((count as f64 * enabled as f64 / running as f64).round() as u64)
+    }
+}
+
+impl Drop for PerfCounter {
+    fn drop(&mut self) {
+        for &fd in self.fds.iter().rev() {
+            unsafe {
+                libc::close(fd);
+            }
+        }
+    }
+}
+
+impl Measurement for PerfCounter {
+    type Intermediate = Option<PerfReading>;
+    type Value = u64;
+
+    fn start(&self) -> Self::Intermediate {
+        self.read()
+    }
+
+    /// Returns 0, with a single notice, if the group could not be read or
+    /// was never scheduled during the measurement.
+    fn end(&self, i: Self::Intermediate) -> Self::Value {
+        let counts = i.zip(self.read()).and_then(|(start, end)| {
+            let enabled = end.enabled - start.enabled;
+            let running = end.running - start.running;
+            let mut counts = [0; MAX_EVENTS];
+            for (n, count) in counts.iter_mut().enumerate().take(self.events.len()) {
+                *count = scale(end.counts[n] - start.counts[n], enabled, running)?;
+            }
+            Some(counts)
+        });
+        match counts {
+            Some(counts) => {
+                let mut totals = self.totals.borrow_mut();
+                for (total, count) in totals.iter_mut().zip(&counts) {
+                    *total += count;
+                }
+                counts[0]
+            }
+            None => {
+                if !self.unscheduled.replace(true) {
+                    eprintln!(
+                        "{} counter unavailable during a measurement, counted as 0",
+                        self.event().name()
+                    );
+                }
+                0
+            }
+        }
+    }
+
+    fn add(&self, v1: &Self::Value, v2: &Self::Value) -> Self::Value {
+        v1 + v2
+    }
+
+    fn zero(&self) -> Self::Value {
+        0
+    }
+
+    fn to_f64(&self, value: &Self::Value) -> f64 {
+        *value as f64
+    }
+
+    /// Criterion asks for the formatter once per benchmark, as it reports
+    /// the results, which is when the totals of the other events are
+    /// reported.
+    fn formatter(&self) -> &dyn ValueFormatter {
+        self.report();
+        self
+    }
+}
+
+/// Formats event counts, and throughputs as counts per byte or per
+/// element, where lower is better.
+impl ValueFormatter for PerfCounter {
+    fn scale_values(&self, typical_value: f64, values: &mut [f64]) -> &'static str {
+        let units = self.event().units();
+        let (factor, unit) = if typical_value < 1e4 {
+            (1.0, units[0])
+        } else if typical_value < 1e7 {
+            (1e-3, units[1])
+        } else {
+            (1e-6, units[2])
+        };
+        for value in values {
+            *value *= factor;
+        }
+        unit
+    }
+
+    fn scale_throughputs(
+        &self,
+        typical_value: f64,
+        throughput: &Throughput,
+        values: &mut [f64],
+    ) -> &'static str {
+        let units = self.event().units();
+        let (count, unit) = match *throughput {
+            Throughput::Bytes(bytes) => (bytes, units[3]),
+            Throughput::Elements(elements) => (elements, units[4]),
+        };
+        // An empty message has no cost per byte.
+        if count == 0 {
+            return self.scale_values(typical_value, values);
+        }
+        for value in values {
+            *value /= count as f64;
+        }
+        unit
+    }
+
+    fn scale_for_machines(&self, _values: &mut [f64]) -> &'static str {
+        self.event().units()[0]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn multiplexing() {
+        assert_eq!(scale(1000, 500, 500), Some(1000));
+        assert_eq!(scale(1000, 800, 200), Some(4000));
+        assert_eq!(scale(1000, 300, 200), Some(1500));
+        assert_eq!(scale(0, 0, 0), Some(0));
+        assert_eq!(scale(0, 100, 0), None);
+    }
+}
@@ -0,0 +1,312 @@
+// SPDX-License-Identifier: Apache-2.0
+
+pub enum SignatureAlgorithm {
+    Rsa2048Pkcs1Sha256,
+    Rsa3072Pkcs1Sha256,
+    Rsa4096Pkcs1Sha256,
+    Rsa2048PssSha256,
+    Rsa3072PssSha256,
+    Rsa4096PssSha256,
+    EcdsaP256Sha256,
+    EcdsaP384Sha384,
+    EcdsaP521Sha512,
+    Ed25519,
+    Ed448,
+    MlDsa44,
+    MlDsa65,
+    MlDsa87,
+}
+
+impl SignatureAlgorithm {
+    pub fn name(&self) -> &str {
+        match self {
+            SignatureAlgorithm::Rsa2048Pkcs1Sha256 => "rsa-2048-pkcs1-sha256",
+            SignatureAlgorithm::Rsa3072Pkcs1Sha256 => "rsa-3072-pkcs1-sha256",
+            SignatureAlgorithm::Rsa4096Pkcs1Sha256 => "rsa-4096-pkcs1-sha256",
+            SignatureAlgorithm::Rsa2048PssSha256 => "rsa-2048-pss-sha256",
+            SignatureAlgorithm::Rsa3072PssSha256 => "rsa-3072-pss-sha256",
+            SignatureAlgorithm::Rsa4096PssSha256 => "rsa-4096-pss-sha256",
+            SignatureAlgorithm::EcdsaP256Sha256 => "ecdsa-p256-sha256",
+            SignatureAlgorithm::EcdsaP384Sha384 => "ecdsa-p384-sha384",
+            SignatureAlgorithm::EcdsaP521Sha512 => "ecdsa-p521-sha512",
+            SignatureAlgorithm::Ed25519 => "ed25519",
+            SignatureAlgorithm::Ed448 => "ed448",
+            SignatureAlgorithm::MlDsa44 => "ml-dsa-44",
+            SignatureAlgorithm::MlDsa65 => "ml-dsa-65",
+            SignatureAlgorithm::MlDsa87 => "ml-dsa-87",
+        }
+    }
+}
+
+pub trait Signature {
+    fn sign(&mut self, msg: &[u8]) -> Vec<u8>;
+    fn verify(&mut self, msg: &[u8], sig: &[u8]) -> bool;
+}
+
+pub trait SignatureBuilder {
+    /// Generates a new key pair.
+    fn generate(&mut self) -> Box<dyn Signature>;
+}
+
+/// Benchmarks signing and verification with one key pair, generated the
+/// first time either benchmark runs rather than when it is registered.
+pub fn bench_signature<B, M>(
+    group: &mut criterion::BenchmarkGroup<M>,
+    algorithm: SignatureAlgorithm,
+    mut builder: B,
+    len: usize,
+) where
+    B: SignatureBuilder,
+    M: criterion::measurement::Measurement,
+{
+    use criterion::black_box;
+    use rand::prelude::*;
+
+    let mut rng = rand::thread_rng();
+
+    let mut msg_bytes = vec![0u8; len];
+    rng.fill(msg_bytes.as_mut_slice());
+
+    let mut key = None;
+    let mut sig = None;
+
+    group.bench_with_input(
+        criterion::BenchmarkId::new(format!("{}/sign", algorithm.name()), len),
+        &msg_bytes,
+        |b, param| {
+            let ctx = key.get_or_insert_with(|| builder.generate());
+            b.iter(|| ctx.sign(black_box(param)));
+        },
+    );
+
+    group.bench_with_input(
+        criterion::BenchmarkId::new(format!("{}/verify", algorithm.name()), len),
+        &msg_bytes,
+        |b, param| {
+            let ctx = key.get_or_insert_with(|| builder.generate());
+            let sig_bytes = sig.get_or_insert_with(|| ctx.sign(param));
+            b.iter(|| {
+                assert!(ctx.verify(black_box(param), black_box(sig_bytes)));
+            });
+        },
+    );
+}
+
+pub enum AsymmetricCipherAlgorithm {
+    Rsa2048OaepSha256,
+    Rsa3072OaepSha256,
+    Rsa4096OaepSha256,
+}
+
+impl AsymmetricCipherAlgorithm {
+    pub fn name(&self) -> &str {
+        match self {
+            AsymmetricCipherAlgorithm::Rsa2048OaepSha256 => "rsa-2048-oaep-sha256",
+            AsymmetricCipherAlgorithm::Rsa3072OaepSha256 => "rsa-3072-oaep-sha256",
+            AsymmetricCipherAlgorithm::Rsa4096OaepSha256 => "rsa-4096-oaep-sha256",
+        }
+    }
+}
+
+pub trait AsymmetricCipher {
+    fn encrypt(&mut self, ptext: &[u8]) -> Vec<u8>;
+    fn decrypt(&mut self, ctext: &[u8]) -> Vec<u8>;
+}
+
+pub trait AsymmetricCipherBuilder {
+    /// Generates a new key pair.
+    fn generate(&mut self) -> Box<dyn AsymmetricCipher>;
+}
+
+/// Benchmarks encryption and decryption with one key pair, generated the
+/// first time either benchmark runs rather than when it is registered.
+pub fn bench_asymmetric_cipher<B, M>(
+    group: &mut criterion::BenchmarkGroup<M>,
+    algorithm: AsymmetricCipherAlgorithm,
+    mut builder: B,
+    len: usize,
+) where
+    B: AsymmetricCipherBuilder,
+    M: criterion::measurement::Measurement,
+{
+    use criterion::black_box;
+    use rand::prelude::*;
+
+    let mut rng = rand::thread_rng();
+
+    let mut pbuf = vec![0u8; len];
+    rng.fill(pbuf.as_mut_slice());
+
+    let mut key = None;
+    let mut cbuf = None;
+
+    group.bench_with_input(
+        criterion::BenchmarkId::new(format!("{}/encrypt", algorithm.name()), len),
+        &pbuf,
+        |b, param| {
+            let ctx = key.get_or_insert_with(|| builder.generate());
+            b.iter(|| ctx.encrypt(black_box(param)));
+        },
+    );
+
+    group.bench_with_input(
+        criterion::BenchmarkId::new(format!("{}/decrypt", algorithm.name()), len),
+        &pbuf,
+        |b, param| {
+            let ctx = key.get_or_insert_with(|| builder.generate());
+            let ctext = cbuf.get_or_insert_with(|| ctx.encrypt(param));
+            b.iter(|| ctx.decrypt(black_box(ctext)));
+        },
+    );
+}
+
+pub enum KeyAgreementAlgorithm {
+    X25519,
+    X448,
+    EcdhP256,
+    EcdhP384,
+    Ffdhe2048,
+    Ffdhe3072,
+    Ffdhe4096,
+}
+
+impl KeyAgreementAlgorithm {
+    pub fn name(&self) -> &str {
+        match self {
+            KeyAgreementAlgorithm::X25519 => "x25519",
+            KeyAgreementAlgorithm::X448 => "x448",
+            KeyAgreementAlgorithm::EcdhP256 => "ecdh-p256",
+            KeyAgreementAlgorithm::EcdhP384 => "ecdh-p384",
+            KeyAgreementAlgorithm::Ffdhe2048 => "ffdhe2048",
+            KeyAgreementAlgorithm::Ffdhe3072 => "ffdhe3072",
+            KeyAgreementAlgorithm::Ffdhe4096 => "ffdhe4096",
+        }
+    }
+}
+
+pub trait KeyAgreement {
+    /// Returns the encoded public key to send to the peer.
+    fn public_key(&self) -> Vec<u8>;
+    /// Computes the shared secret from the peer's encoded public key.
+    fn derive(&mut self, peer: &[u8]) -> Vec<u8>;
+}
+
+pub trait KeyAgreementBuilder {
+    /// Generates a new key pair.
+    fn generate(&mut self) -> Box<dyn KeyAgreement>;
+}
+
+/// Benchmarks key pair generation, and shared secret derivation
+/// including the decoding of the peer's public key.
+pub fn bench_key_agreement<B, M>(
+    group: &mut criterion::BenchmarkGroup<M>,
+    algorithm: KeyAgreementAlgorithm,
+    mut builder: B,
+) where
+    B: KeyAgreementBuilder,
+    M: criterion::measurement::Measurement,
+{
+    use criterion::black_box;
+
+    let mut keys = None;
+
+    group.bench_function(
+        criterion::BenchmarkId::new(algorithm.name(), "generate"),
+        |b| {
+            b.iter(|| builder.generate());
+        },
+    );
+
+    group.bench_function(
+        criterion::BenchmarkId::new(algorithm.name(), "derive"),
+        |b| {
+            let (ctx, peer) = keys.get_or_insert_with(|| {
+                let ctx = builder.generate();
+                let peer = builder.generate().public_key();
+                (ctx, peer)
+            });
+            b.iter(|| ctx.derive(black_box(peer)));
+        },
+    );
+}
+
+pub enum KemAlgorithm {
+    MlKem512,
+    MlKem768,
+    MlKem1024,
+    X25519MlKem768,
+}
+
+impl KemAlgorithm {
+    pub fn name(&self) -> &str {
+        match self {
+            KemAlgorithm::MlKem512 => "ml-kem-512",
+            KemAlgorithm::MlKem768 => "ml-kem-768",
+            KemAlgorithm::MlKem1024 => "ml-kem-1024",
+            KemAlgorithm::X25519MlKem768 => "x25519-ml-kem-768",
+        }
+    }
+}
+
+pub trait Kem {
+    /// Returns the encoded encapsulation key.
+    fn public_key(&self) -> Vec<u8>;
+    /// Encapsulates a fresh shared secret to the peer's encoded
+    /// encapsulation key, returning the ciphertext and the shared secret.
+    fn encapsulate(&mut self, peer: &[u8]) -> (Vec<u8>, Vec<u8>);
+    /// Recovers the shared secret from a ciphertext.
+    fn decapsulate(&mut self, ctext: &[u8]) -> Vec<u8>;
+}
+
+pub trait KemBuilder {
+    /// Generates a new key pair.
+    fn generate(&mut self) -> Box<dyn Kem>;
+}
+
+/// Benchmarks key pair generation, encapsulation including the decoding
+/// of the peer's encapsulation key, and decapsulation.
+pub fn bench_kem<B, M>(
+    group: &mut criterion::BenchmarkGroup<M>,
+    algorithm: KemAlgorithm,
+    mut builder: B,
+) where
+    B: KemBuilder,
+    M: criterion::measurement::Measurement,
+{
+    use criterion::black_box;
+
+    let mut key = None;
+    let mut encapsulated = None;
+
+    group.bench_function(
+        criterion::BenchmarkId::new(algorithm.name(), "generate"),
+        |b| {
+            b.iter(|| builder.generate());
+        },
+    );
+
+    group.bench_function(
+        criterion::BenchmarkId::new(algorithm.name(), "encapsulate"),
+        |b| {
+            let (ctx, peer) = key.get_or_insert_with(|| {
+                let ctx = builder.generate();
+                let peer = ctx.public_key();
+                (ctx, peer)
+            });
+            b.iter(|| ctx.encapsulate(black_box(peer)));
+        },
+    );
+
+    group.bench_function(
+        criterion::BenchmarkId::new(algorithm.name(), "decapsulate"),
+        |b| {
+            let (ctx, peer) = key.get_or_insert_with(|| {
+                let ctx = builder.generate();
+                let peer = ctx.public_key();
+                (ctx, peer)
+            });
+            let (ctext, _) = encapsulated.get_or_insert_with(|| ctx.encapsulate(peer));
+            b.iter(|| ctx.decapsulate(black_box(ctext)));
+        },
+    );
+}
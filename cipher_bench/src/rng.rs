@@ -0,0 +1,102 @@
+// SPDX-License-Identifier: Apache-2.0
+
+pub enum RngAlgorithm {
+    RandBytes,
+    RandPrivBytes,
+    CtrDrbgAes256,
+    HashDrbgSha256,
+    Yarrow256,
+    KnuthLfib,
+    Getrandom,
+}
+
+impl RngAlgorithm {
+    pub fn name(&self) -> &str {
+        match self {
+            RngAlgorithm::RandBytes => "rand-bytes",
+            RngAlgorithm::RandPrivBytes => "rand-priv-bytes",
+            RngAlgorithm::CtrDrbgAes256 => "ctr-drbg-aes-256",
+            RngAlgorithm::HashDrbgSha256 => "hash-drbg-sha256",
+            RngAlgorithm::Yarrow256 => "yarrow256",
+            RngAlgorithm::KnuthLfib => "knuth-lfib",
+            RngAlgorithm::Getrandom => "getrandom",
+        }
+    }
+}
+
+pub trait Rng {
+    fn fill(&mut self, dst: &mut [u8]);
+}
+
+pub trait RngBuilder {
+    /// Creates and seeds a generator.
+    fn for_generation(&mut self) -> Box<dyn Rng>;
+}
+
+/// The getrandom(2) system call, as a baseline for the user-space
+/// generators.
+#[derive(Default)]
+pub struct GetrandomCtxBuilder;
+
+impl GetrandomCtxBuilder {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl RngBuilder for GetrandomCtxBuilder {
+    fn for_generation(&mut self) -> Box<dyn Rng> {
+        Box::new(GetrandomCtx)
+    }
+}
+
+pub struct GetrandomCtx;
+
+impl Rng for GetrandomCtx {
+    fn fill(&mut self, dst: &mut [u8]) {
+        // Reads from the urandom source return at most 32 MiB at a time,
+        // and fills above 256 bytes can be interrupted by a signal.
+        let mut filled = 0;
+        while filled < dst.len() {
+            let rest = &mut dst[filled..];
+            let ret = unsafe { libc::getrandom(rest.as_mut_ptr() as *mut _, rest.len(), 0) };
+            if ret < 0 {
+                let e = std::io::Error::last_os_error();
+                if e.kind() == std::io::ErrorKind::Interrupted {
+                    continue;
+                }
+                panic!("getrandom: {}", e);
+            }
+            filled += ret as usize;
+        }
+    }
+}
+
+/// Sizes from a 16-byte nonce to a 1 MiB fill, in powers of four.
+pub const RNG_LENGTHS: [usize; 9] = [16, 64, 256, 1024, 4096, 16384, 65536, 262144, 1048576];
+
+pub fn bench_rng<B, M>(
+    group: &mut criterion::BenchmarkGroup<M>,
+    algorithm: RngAlgorithm,
+    mut builder: B,
+    len: usize,
+) where
+    B: RngBuilder,
+    M: criterion::measurement::Measurement,
+{
+    group.bench_with_input(
+        criterion::BenchmarkId::new(algorithm.name(), len),
+        &len,
+        |b, param| {
+            use criterion::black_box;
+
+            let mut ctx = builder.for_generation();
+
+            let mut buf = vec![0u8; *param];
+
+            b.iter(|| {
+                ctx.fill(black_box(&mut buf));
+            });
+        },
+    );
+}
@@ -0,0 +1,241 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::env;
+use std::fmt;
+use std::fs;
+
+/// The message sizes, in bytes, a benchmark is run over.
+///
+/// A sweep is written as one of:
+///
+/// | spec                | sizes                           |
+/// |---------------------|---------------------------------|
+/// | `linear:1K*8`       | 1 KiB, 2 KiB, …, 8 KiB          |
+/// | `pow2:16-16M`       | 16 B, 32 B, …, 16 MiB           |
+/// | `list:0,1K,64K`     | exactly the given sizes         |
+///
+/// where sizes take an optional `K`, `M` or `G` binary suffix.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SizeSweep {
+    /// `count` multiples of `step`, starting at `step`.
+    Linear {
+        step: usize,
+        count: usize,
+    },
+    /// The powers of two from `min` to `max`, both inclusive.
+    PowersOfTwo {
+        min: usize,
+        max: usize,
+    },
+    List(Vec<usize>),
+}
+
+impl Default for SizeSweep {
+    /// 1 KiB to 8 KiB in 1 KiB steps.
+    fn default() -> Self {
+        SizeSweep::Linear {
+            step: 1024,
+            count: 8,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ParseSizeSweepError {
+    spec: String,
+    /// The alignment a size was not a multiple of, if the spec parsed.
+    alignment: Option<usize>,
+}
+
+impl fmt::Display for ParseSizeSweepError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.alignment {
+            Some(alignment) => write!(
+                f,
+                "size sweep `{}` has sizes that are not multiples of {} bytes",
+                self.spec, alignment
+            ),
+            None => write!(f, "invalid size sweep `{}`", self.spec),
+        }
+    }
+}
+
+impl std::error::Error for ParseSizeSweepError {}
+
+fn parse_size(s: &str) -> Option<usize> {
+    let s = s.trim();
+    let (digits, shift) = match s.as_bytes().last()? {
+        b'K' | b'k' => (&s[..s.len() - 1], 10),
+        b'M' | b'm' => (&s[..s.len() - 1], 20),
+        b'G' | b'g' => (&s[..s.len() - 1], 30),
+        _ => (s, 0),
+    };
+    digits.parse::<usize>().ok()?.checked_mul(1 << shift)
+}
+
+impl SizeSweep {
+    pub fn parse(spec: &str) -> Result<Self, ParseSizeSweepError> {
+        let error = || ParseSizeSweepError {
+            spec: spec.to_string(),
+            alignment: None,
+        };
+
+        let (kind, args) = spec.trim().split_once(':').ok_or_else(error)?;
+        let sweep = match kind {
+            "linear" => {
+                let (step, count) = args.split_once('*').ok_or_else(error)?;
+                SizeSweep::Linear {
+                    step: parse_size(step).ok_or_else(error)?,
+                    count: count.trim().parse().map_err(|_| error())?,
+                }
+            }
+            "pow2" => {
+                let (min, max) = args.split_once('-').ok_or_else(error)?;
+                let min = parse_size(min).ok_or_else(error)?;
+                let max = parse_size(max).ok_or_else(error)?;
+                if !min.is_power_of_two() || min > max {
+                    return Err(error());
+                }
+                SizeSweep::PowersOfTwo { min, max }
+            }
+            "list" => SizeSweep::List(
+                args.split(',')
+                    .map(parse_size)
+                    .collect::<Option<_>>()
+                    .ok_or_else(error)?,
+            ),
+            _ => return Err(error()),
+        };
+        Ok(sweep)
+    }
+
+    /// Parses `spec` like [`parse`](Self::parse), and rejects it unless
+    /// all of its sizes are multiples of `alignment`.
+    pub fn parse_aligned(spec: &str, alignment: usize) -> Result<Self, ParseSizeSweepError> {
+        let sweep = Self::parse(spec)?;
+        if !sweep.is_aligned(alignment) {
+            return Err(ParseSizeSweepError {
+                spec: spec.to_string(),
+                alignment: Some(alignment),
+            });
+        }
+        Ok(sweep)
+    }
+
+    fn is_aligned(&self, alignment: usize) -> bool {
+        self.sizes().iter().all(|size| size % alignment == 0)
+    }
+
+    /// Returns the sweep for the benchmark `name`, taken from the first
+    /// of:
+    ///
+    /// 1. the `CIPHER_BENCH_SWEEP_<NAME>` environment variable, with
+    ///    `name` upper-cased and `-` replaced by `_`,
+    /// 2. the `CIPHER_BENCH_SWEEP` environment variable,
+    /// 3. a `<name> = <spec>` or `default = <spec>` line in the file
+    ///    named by the `CIPHER_BENCH_CONFIG` environment variable,
+    /// 4. `default`.
+    ///
+    /// Benchmarks that stream their input in blocks, such as CBC or GCM,
+    /// pass the block size as `alignment`, and 1 otherwise.
+    ///
+    /// Panics if the chosen spec does not parse or has a size that is not
+    /// a multiple of `alignment`, rather than silently running a sweep
+    /// that was not asked for.
+    pub fn for_benchmark(name: &str, alignment: usize, default: SizeSweep) -> Self {
+        let var = format!(
+            "CIPHER_BENCH_SWEEP_{}",
+            name.to_uppercase().replace('-', "_")
+        );
+        let spec = env::var(var)
+            .or_else(|_| env::var("CIPHER_BENCH_SWEEP"))
+            .ok()
+            .or_else(|| {
+                let path = env::var("CIPHER_BENCH_CONFIG").ok()?;
+                let config = fs::read_to_string(&path)
+                    .unwrap_or_else(|e| panic!("cannot read {}: {}", path, e));
+                config_lookup(&config, name).or_else(|| config_lookup(&config, "default"))
+            });
+
+        match spec {
+            Some(spec) => Self::parse_aligned(&spec, alignment).unwrap_or_else(|e| panic!("{}", e)),
+            None => {
+                debug_assert!(default.is_aligned(alignment));
+                default
+            }
+        }
+    }
+
+    pub fn sizes(&self) -> Vec<usize> {
+        match self {
+            SizeSweep::Linear { step, count } => (1..=*count).map(|i| i * step).collect(),
+            SizeSweep::PowersOfTwo { min, max } => {
+                let mut sizes = Vec::new();
+                let mut size = *min;
+                while size <= *max {
+                    sizes.push(size);
+                    size = match size.checked_mul(2) {
+                        Some(size) => size,
+                        None => break,
+                    };
+                }
+                sizes
+            }
+            SizeSweep::List(sizes) => sizes.clone(),
+        }
+    }
+}
+
+/// Finds the spec of `name` in a config file made of `name = spec` lines,
+/// where `#` starts a comment.
+fn config_lookup(config: &str, name: &str) -> Option<String> {
+    config.lines().find_map(|line| {
+        let line = line.split('#').next().unwrap();
+        let (key, value) = line.split_once('=')?;
+        if key.trim() == name {
+            Some(value.trim().to_string())
+        } else {
+            None
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_sizes() {
+        assert_eq!(
+            SizeSweep::parse("linear:1K*8").unwrap(),
+            SizeSweep::default()
+        );
+        assert_eq!(SizeSweep::parse("pow2:16-16M").unwrap().sizes().len(), 21);
+        assert_eq!(
+            SizeSweep::parse("list:0, 100, 64K").unwrap().sizes(),
+            vec![0, 100, 65536]
+        );
+        assert!(SizeSweep::parse("pow2:24-1K").is_err());
+        assert!(SizeSweep::parse("linear:1K").is_err());
+        assert!(SizeSweep::parse("1K").is_err());
+    }
+
+    #[test]
+    fn alignment() {
+        assert!(SizeSweep::parse_aligned("linear:1K*8", 16).is_ok());
+        assert!(SizeSweep::parse_aligned("pow2:16-1K", 16).is_ok());
+        assert!(SizeSweep::parse_aligned("list:0,16,1K", 16).is_ok());
+        assert!(SizeSweep::parse_aligned("list:16,20,1K", 16).is_err());
+        assert!(SizeSweep::parse_aligned("pow2:1-1K", 16).is_err());
+        assert!(SizeSweep::parse_aligned("linear:24*4", 16).is_err());
+        assert!(SizeSweep::parse_aligned("list:16,20,1K", 1).is_ok());
+    }
+
+    #[test]
+    fn config() {
+        let config = "# sweeps\ndefault = pow2:16-1K\naead = list:16 # small\n";
+        assert_eq!(config_lookup(config, "aead").unwrap(), "list:16");
+        assert_eq!(config_lookup(config, "default").unwrap(), "pow2:16-1K");
+        assert!(config_lookup(config, "block").is_none());
+    }
+}
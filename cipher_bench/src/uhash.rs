@@ -0,0 +1,62 @@
+// SPDX-License-Identifier: Apache-2.0
+
+/// Universal hashes are benchmarked through the MAC interface, feeding
+/// the input as associated data of the AEAD or MAC they belong to, so
+/// the results can be compared with the corresponding AEAD numbers.
+pub enum UniversalHashAlgorithm {
+    Ghash,
+    /// POLYVAL as AES-128-GCM-SIV computes it over the associated data of
+    /// an empty message. Neither library exposes POLYVAL on its own, so
+    /// every update is a complete message and the result also includes
+    /// the per-nonce key derivation and the encryption of the tag.
+    Polyval,
+    Poly1305,
+}
+
+impl UniversalHashAlgorithm {
+    pub fn name(&self) -> &str {
+        match self {
+            UniversalHashAlgorithm::Ghash => "ghash",
+            UniversalHashAlgorithm::Polyval => "polyval-gcm-siv",
+            UniversalHashAlgorithm::Poly1305 => "poly1305",
+        }
+    }
+
+    pub fn key_len(&self) -> usize {
+        match self {
+            UniversalHashAlgorithm::Ghash => 16,
+            UniversalHashAlgorithm::Polyval => 16,
+            UniversalHashAlgorithm::Poly1305 => 32,
+        }
+    }
+
+    pub fn nonce_len(&self) -> usize {
+        match self {
+            UniversalHashAlgorithm::Ghash => 12,
+            UniversalHashAlgorithm::Polyval => 12,
+            UniversalHashAlgorithm::Poly1305 => 16,
+        }
+    }
+}
+
+/// Benchmarks hashing a stream of `len` byte updates without finalising,
+/// as the AEADs do in [`bench_aead`](crate::bench_aead).
+pub fn bench_universal_hash<B, M>(
+    group: &mut criterion::BenchmarkGroup<M>,
+    algorithm: UniversalHashAlgorithm,
+    builder: B,
+    len: usize,
+) where
+    B: crate::MacBuilder,
+    M: criterion::measurement::Measurement,
+{
+    crate::bench_mac_input(
+        group,
+        algorithm.name(),
+        algorithm.key_len(),
+        algorithm.nonce_len(),
+        None,
+        builder,
+        len,
+    );
+}
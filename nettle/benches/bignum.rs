@@ -0,0 +1,29 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+
+use cipher_bench::{bench_bignum, BignumOperation, SelectedMeasurement};
+use nettle::GmpCtxBuilder;
+
+pub fn bignum(c: &mut Criterion<SelectedMeasurement>) {
+    let mut group = c.benchmark_group("nettle/bignum");
+    group.throughput(Throughput::Elements(1));
+    group.sample_size(10);
+
+    for bits in [1024, 2048, 3072, 4096, 8192] {
+        let builder = GmpCtxBuilder::new();
+        bench_bignum(&mut group, BignumOperation::ModExpConsttime, builder, bits);
+
+        let builder = GmpCtxBuilder::new();
+        bench_bignum(&mut group, BignumOperation::ModMul, builder, bits);
+    }
+
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    config = cipher_bench::criterion();
+    targets = bignum
+}
+criterion_main!(benches);
@@ -2,25 +2,39 @@
 
 use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
 
-use cipher_bench::{bench_block, BlockCipherAlgorithm};
+use cipher_bench::{
+    bench_block, bench_block_setup, BlockCipherAlgorithm, SelectedMeasurement, SizeSweep,
+};
 use nettle::Aes128CbcCtxBuilder;
 use std::convert::TryInto;
 
-pub fn block_ciphers(c: &mut Criterion) {
+pub fn block_ciphers(c: &mut Criterion<SelectedMeasurement>) {
     let mut group = c.benchmark_group("nettle/block-ciphers");
-    let parameters: Vec<usize> = (1..=cipher_bench::ITER).collect();
+    let sweep = SizeSweep::for_benchmark("block-ciphers", 16, SizeSweep::default());
 
-    for i in parameters {
-        group.throughput(Throughput::Bytes(
-            (i * cipher_bench::STEP).try_into().unwrap(),
-        ));
+    for len in sweep.sizes() {
+        group.throughput(Throughput::Bytes(len.try_into().unwrap()));
 
         let builder = Aes128CbcCtxBuilder::new();
-        bench_block(&mut group, BlockCipherAlgorithm::Aes128Cbc, builder, i);
+        bench_block(&mut group, BlockCipherAlgorithm::Aes128Cbc, builder, len);
     }
 
     group.finish();
 }
 
-criterion_group!(benches, block_ciphers);
+pub fn block_cipher_setup(c: &mut Criterion<SelectedMeasurement>) {
+    let mut group = c.benchmark_group("nettle/block-cipher-setup");
+    group.throughput(Throughput::Elements(1));
+
+    let builder = Aes128CbcCtxBuilder::new();
+    bench_block_setup(&mut group, BlockCipherAlgorithm::Aes128Cbc, builder);
+
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    config = cipher_bench::criterion();
+    targets = block_ciphers, block_cipher_setup
+}
 criterion_main!(benches);
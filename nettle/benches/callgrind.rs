@@ -0,0 +1,26 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use cipher_bench::{
+    callgrind_aead, callgrind_block, AeadAlgorithm, BlockCipherAlgorithm, Callgrind, SizeSweep,
+};
+use nettle::{Aes128CbcCtxBuilder, Aes128GcmCtxBuilder};
+
+// Instruction counts and simulated cache behaviour of the block cipher
+// and AEAD cases, over the same sweeps as the criterion benches.
+fn main() {
+    let mut runner = Callgrind::new("nettle");
+
+    let sweep = SizeSweep::for_benchmark("block-ciphers", 16, SizeSweep::default());
+    for len in sweep.sizes() {
+        let builder = Aes128CbcCtxBuilder::new();
+        callgrind_block(&mut runner, BlockCipherAlgorithm::Aes128Cbc, builder, len);
+    }
+
+    let sweep = SizeSweep::for_benchmark("aeads", 16, SizeSweep::default());
+    for len in sweep.sizes() {
+        let builder = Aes128GcmCtxBuilder::new();
+        callgrind_aead(&mut runner, AeadAlgorithm::Aes128Gcm, builder, len);
+    }
+
+    runner.run();
+}
@@ -0,0 +1,29 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+
+use cipher_bench::{bench_scalar_mul, CurveAlgorithm, SelectedMeasurement};
+use nettle::{Curve25519ScalarMulCtxBuilder, P256ScalarMulCtxBuilder, P384ScalarMulCtxBuilder};
+
+pub fn scalar_mul(c: &mut Criterion<SelectedMeasurement>) {
+    let mut group = c.benchmark_group("nettle/scalar-mul");
+    group.throughput(Throughput::Elements(1));
+
+    let builder = P256ScalarMulCtxBuilder::new();
+    bench_scalar_mul(&mut group, CurveAlgorithm::P256, builder);
+
+    let builder = P384ScalarMulCtxBuilder::new();
+    bench_scalar_mul(&mut group, CurveAlgorithm::P384, builder);
+
+    let builder = Curve25519ScalarMulCtxBuilder::new();
+    bench_scalar_mul(&mut group, CurveAlgorithm::Curve25519, builder);
+
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    config = cipher_bench::criterion();
+    targets = scalar_mul
+}
+criterion_main!(benches);
@@ -0,0 +1,34 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use cipher_bench::{bench_kdf, bench_tls13_key_schedule, KdfAlgorithm, SelectedMeasurement};
+use nettle::{HkdfSha256CtxBuilder, HkdfSha384CtxBuilder};
+
+pub fn kdfs(c: &mut Criterion<SelectedMeasurement>) {
+    let mut group = c.benchmark_group("nettle/kdfs");
+    let parameters: Vec<usize> = vec![16, 32, 64];
+
+    for len in parameters {
+        let builder = HkdfSha256CtxBuilder::new();
+        bench_kdf(&mut group, KdfAlgorithm::HkdfSha256, builder, len);
+
+        let builder = HkdfSha384CtxBuilder::new();
+        bench_kdf(&mut group, KdfAlgorithm::HkdfSha384, builder, len);
+    }
+
+    let builder = HkdfSha256CtxBuilder::new();
+    bench_tls13_key_schedule(&mut group, KdfAlgorithm::HkdfSha256, builder, 16);
+
+    let builder = HkdfSha384CtxBuilder::new();
+    bench_tls13_key_schedule(&mut group, KdfAlgorithm::HkdfSha384, builder, 32);
+
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    config = cipher_bench::criterion();
+    targets = kdfs
+}
+criterion_main!(benches);
@@ -0,0 +1,33 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+
+use cipher_bench::{bench_key_decoder, KeyAlgorithm, KeyEncoding, KeyFormat, SelectedMeasurement};
+use nettle::RsaKeyDecoderCtxBuilder;
+
+pub fn decoder(c: &mut Criterion<SelectedMeasurement>) {
+    let mut group = c.benchmark_group("nettle/key-decoder");
+    group.throughput(Throughput::Elements(1));
+
+    // nettle only parses RSA keys, and has no support for encrypted
+    // PKCS#8.
+    for format in [
+        KeyFormat::TypeSpecific,
+        KeyFormat::Pkcs8,
+        KeyFormat::SubjectPublicKeyInfo,
+    ] {
+        for encoding in [KeyEncoding::Der, KeyEncoding::Pem] {
+            let builder = RsaKeyDecoderCtxBuilder::new();
+            bench_key_decoder(&mut group, KeyAlgorithm::Rsa2048, format, encoding, builder);
+        }
+    }
+
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    config = cipher_bench::criterion();
+    targets = decoder
+}
+criterion_main!(benches);
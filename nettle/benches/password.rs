@@ -0,0 +1,65 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use cipher_bench::{
+    bench_password_hash, PasswordHashAlgorithm, PasswordHashCost, SelectedMeasurement,
+};
+#[cfg(have_balloon)]
+use nettle::BalloonSha256CtxBuilder;
+use nettle::{Pbkdf2Sha256CtxBuilder, Pbkdf2Sha512CtxBuilder};
+
+pub fn password_hashes(c: &mut Criterion<SelectedMeasurement>) {
+    let mut group = c.benchmark_group("nettle/password-hashes");
+    group.sample_size(10);
+
+    for iterations in [1_000, 10_000, 100_000] {
+        let cost = PasswordHashCost {
+            time: iterations,
+            memory: 0,
+            parallelism: 0,
+        };
+
+        let builder = Pbkdf2Sha256CtxBuilder::new();
+        bench_password_hash(
+            &mut group,
+            PasswordHashAlgorithm::Pbkdf2Sha256,
+            builder,
+            cost,
+        );
+
+        let builder = Pbkdf2Sha512CtxBuilder::new();
+        bench_password_hash(
+            &mut group,
+            PasswordHashAlgorithm::Pbkdf2Sha512,
+            builder,
+            cost,
+        );
+    }
+
+    #[cfg(have_balloon)]
+    for blocks in [1_024, 16_384, 65_536] {
+        let cost = PasswordHashCost {
+            time: 3,
+            memory: blocks,
+            parallelism: 0,
+        };
+
+        let builder = BalloonSha256CtxBuilder::new();
+        bench_password_hash(
+            &mut group,
+            PasswordHashAlgorithm::BalloonSha256,
+            builder,
+            cost,
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    config = cipher_bench::criterion();
+    targets = password_hashes
+}
+criterion_main!(benches);
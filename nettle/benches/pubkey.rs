@@ -0,0 +1,275 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+
+use cipher_bench::SelectedMeasurement;
+#[cfg(have_rsa_oaep)]
+use cipher_bench::{bench_asymmetric_cipher, AsymmetricCipherAlgorithm};
+use cipher_bench::{bench_key_agreement, KeyAgreementAlgorithm};
+use cipher_bench::{bench_keygen, bench_signature, SignatureAlgorithm, SizeSweep};
+#[cfg(have_rsa_oaep)]
+use nettle::RsaOaepSha256CtxBuilder;
+use nettle::{EcdhP256CtxBuilder, EcdhP384CtxBuilder, X25519CtxBuilder, X448CtxBuilder};
+use nettle::{EcdsaP256Sha256CtxBuilder, EcdsaP384Sha384CtxBuilder, EcdsaP521Sha512CtxBuilder};
+use nettle::{Ed25519CtxBuilder, Ed448CtxBuilder};
+use nettle::{Ffdhe2048CtxBuilder, Ffdhe3072CtxBuilder, Ffdhe4096CtxBuilder};
+use nettle::{RsaPkcs1Sha256CtxBuilder, RsaPssSha256CtxBuilder};
+use std::convert::TryInto;
+
+// Size of the signed messages and of the encrypted plaintexts.
+const LEN: usize = 32;
+
+const KEYGEN: &str = "nettle/keygen";
+
+pub fn signatures(c: &mut Criterion<SelectedMeasurement>) {
+    let mut group = c.benchmark_group("nettle/signatures");
+    group.throughput(Throughput::Elements(1));
+
+    let builder = RsaPkcs1Sha256CtxBuilder::new(2048);
+    bench_signature(
+        &mut group,
+        SignatureAlgorithm::Rsa2048Pkcs1Sha256,
+        builder,
+        LEN,
+    );
+
+    let builder = RsaPkcs1Sha256CtxBuilder::new(3072);
+    bench_signature(
+        &mut group,
+        SignatureAlgorithm::Rsa3072Pkcs1Sha256,
+        builder,
+        LEN,
+    );
+
+    let builder = RsaPkcs1Sha256CtxBuilder::new(4096);
+    bench_signature(
+        &mut group,
+        SignatureAlgorithm::Rsa4096Pkcs1Sha256,
+        builder,
+        LEN,
+    );
+
+    let builder = RsaPssSha256CtxBuilder::new(2048);
+    bench_signature(
+        &mut group,
+        SignatureAlgorithm::Rsa2048PssSha256,
+        builder,
+        LEN,
+    );
+
+    let builder = RsaPssSha256CtxBuilder::new(3072);
+    bench_signature(
+        &mut group,
+        SignatureAlgorithm::Rsa3072PssSha256,
+        builder,
+        LEN,
+    );
+
+    let builder = RsaPssSha256CtxBuilder::new(4096);
+    bench_signature(
+        &mut group,
+        SignatureAlgorithm::Rsa4096PssSha256,
+        builder,
+        LEN,
+    );
+
+    let builder = EcdsaP256Sha256CtxBuilder::new();
+    bench_signature(
+        &mut group,
+        SignatureAlgorithm::EcdsaP256Sha256,
+        builder,
+        LEN,
+    );
+
+    let builder = EcdsaP384Sha384CtxBuilder::new();
+    bench_signature(
+        &mut group,
+        SignatureAlgorithm::EcdsaP384Sha384,
+        builder,
+        LEN,
+    );
+
+    let builder = EcdsaP521Sha512CtxBuilder::new();
+    bench_signature(
+        &mut group,
+        SignatureAlgorithm::EcdsaP521Sha512,
+        builder,
+        LEN,
+    );
+
+    // The nettle versions this crate builds against provide neither
+    // ML-DSA nor ML-KEM, so the post-quantum algorithms are only
+    // benchmarked with OpenSSL.
+
+    group.finish();
+}
+
+// EdDSA hashes the whole message twice when signing, so it is swept over
+// message sizes like the symmetric primitives.
+pub fn eddsa(c: &mut Criterion<SelectedMeasurement>) {
+    let mut group = c.benchmark_group("nettle/eddsa");
+    let sweep = SizeSweep::for_benchmark("eddsa", 1, SizeSweep::default());
+
+    for len in sweep.sizes() {
+        group.throughput(Throughput::Bytes(len.try_into().unwrap()));
+
+        let builder = Ed25519CtxBuilder::new();
+        bench_signature(&mut group, SignatureAlgorithm::Ed25519, builder, len);
+
+        let builder = Ed448CtxBuilder::new();
+        bench_signature(&mut group, SignatureAlgorithm::Ed448, builder, len);
+    }
+
+    group.finish();
+}
+
+#[cfg(have_rsa_oaep)]
+pub fn asymmetric_ciphers(c: &mut Criterion<SelectedMeasurement>) {
+    let mut group = c.benchmark_group("nettle/asymmetric-ciphers");
+    group.throughput(Throughput::Elements(1));
+
+    let builder = RsaOaepSha256CtxBuilder::new(2048);
+    bench_asymmetric_cipher(
+        &mut group,
+        AsymmetricCipherAlgorithm::Rsa2048OaepSha256,
+        builder,
+        LEN,
+    );
+
+    let builder = RsaOaepSha256CtxBuilder::new(3072);
+    bench_asymmetric_cipher(
+        &mut group,
+        AsymmetricCipherAlgorithm::Rsa3072OaepSha256,
+        builder,
+        LEN,
+    );
+
+    let builder = RsaOaepSha256CtxBuilder::new(4096);
+    bench_asymmetric_cipher(
+        &mut group,
+        AsymmetricCipherAlgorithm::Rsa4096OaepSha256,
+        builder,
+        LEN,
+    );
+
+    group.finish();
+}
+
+#[cfg(not(have_rsa_oaep))]
+pub fn asymmetric_ciphers(_c: &mut Criterion<SelectedMeasurement>) {}
+
+pub fn key_agreements(c: &mut Criterion<SelectedMeasurement>) {
+    let mut group = c.benchmark_group("nettle/key-agreements");
+    group.throughput(Throughput::Elements(1));
+
+    let builder = X25519CtxBuilder::new();
+    bench_key_agreement(&mut group, KeyAgreementAlgorithm::X25519, builder);
+
+    let builder = X448CtxBuilder::new();
+    bench_key_agreement(&mut group, KeyAgreementAlgorithm::X448, builder);
+
+    let builder = EcdhP256CtxBuilder::new();
+    bench_key_agreement(&mut group, KeyAgreementAlgorithm::EcdhP256, builder);
+
+    let builder = EcdhP384CtxBuilder::new();
+    bench_key_agreement(&mut group, KeyAgreementAlgorithm::EcdhP384, builder);
+
+    let builder = Ffdhe2048CtxBuilder::new();
+    bench_key_agreement(&mut group, KeyAgreementAlgorithm::Ffdhe2048, builder);
+
+    let builder = Ffdhe3072CtxBuilder::new();
+    bench_key_agreement(&mut group, KeyAgreementAlgorithm::Ffdhe3072, builder);
+
+    let builder = Ffdhe4096CtxBuilder::new();
+    bench_key_agreement(&mut group, KeyAgreementAlgorithm::Ffdhe4096, builder);
+
+    group.finish();
+}
+
+pub fn keygen(c: &mut Criterion<SelectedMeasurement>) {
+    let measurement = SelectedMeasurement::from_env();
+    let mut group = c.benchmark_group(KEYGEN);
+    group.throughput(Throughput::Elements(1));
+    group.sample_size(10);
+
+    let builder = RsaPkcs1Sha256CtxBuilder::new(2048);
+    bench_keygen(
+        &mut group,
+        KEYGEN,
+        &measurement,
+        SignatureAlgorithm::Rsa2048Pkcs1Sha256,
+        builder,
+    );
+
+    let builder = RsaPkcs1Sha256CtxBuilder::new(3072);
+    bench_keygen(
+        &mut group,
+        KEYGEN,
+        &measurement,
+        SignatureAlgorithm::Rsa3072Pkcs1Sha256,
+        builder,
+    );
+
+    let builder = RsaPkcs1Sha256CtxBuilder::new(4096);
+    bench_keygen(
+        &mut group,
+        KEYGEN,
+        &measurement,
+        SignatureAlgorithm::Rsa4096Pkcs1Sha256,
+        builder,
+    );
+
+    let builder = EcdsaP256Sha256CtxBuilder::new();
+    bench_keygen(
+        &mut group,
+        KEYGEN,
+        &measurement,
+        SignatureAlgorithm::EcdsaP256Sha256,
+        builder,
+    );
+
+    let builder = EcdsaP384Sha384CtxBuilder::new();
+    bench_keygen(
+        &mut group,
+        KEYGEN,
+        &measurement,
+        SignatureAlgorithm::EcdsaP384Sha384,
+        builder,
+    );
+
+    let builder = EcdsaP521Sha512CtxBuilder::new();
+    bench_keygen(
+        &mut group,
+        KEYGEN,
+        &measurement,
+        SignatureAlgorithm::EcdsaP521Sha512,
+        builder,
+    );
+
+    let builder = Ed25519CtxBuilder::new();
+    bench_keygen(
+        &mut group,
+        KEYGEN,
+        &measurement,
+        SignatureAlgorithm::Ed25519,
+        builder,
+    );
+
+    let builder = Ed448CtxBuilder::new();
+    bench_keygen(
+        &mut group,
+        KEYGEN,
+        &measurement,
+        SignatureAlgorithm::Ed448,
+        builder,
+    );
+
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    config = cipher_bench::criterion();
+    targets = signatures, eddsa, asymmetric_ciphers, key_agreements, keygen
+}
+criterion_main!(benches);
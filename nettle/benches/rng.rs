@@ -0,0 +1,36 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+
+use cipher_bench::{
+    bench_rng, GetrandomCtxBuilder, RngAlgorithm, SelectedMeasurement, SizeSweep, RNG_LENGTHS,
+};
+use nettle::{KnuthLfibCtxBuilder, Yarrow256CtxBuilder};
+use std::convert::TryInto;
+
+pub fn rng(c: &mut Criterion<SelectedMeasurement>) {
+    let mut group = c.benchmark_group("nettle/rng");
+    let sweep = SizeSweep::for_benchmark("rng", 1, SizeSweep::List(RNG_LENGTHS.to_vec()));
+
+    for len in sweep.sizes() {
+        group.throughput(Throughput::Bytes(len.try_into().unwrap()));
+
+        let builder = Yarrow256CtxBuilder::new();
+        bench_rng(&mut group, RngAlgorithm::Yarrow256, builder, len);
+
+        let builder = KnuthLfibCtxBuilder::new();
+        bench_rng(&mut group, RngAlgorithm::KnuthLfib, builder, len);
+
+        let builder = GetrandomCtxBuilder::new();
+        bench_rng(&mut group, RngAlgorithm::Getrandom, builder, len);
+    }
+
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    config = cipher_bench::criterion();
+    targets = rng
+}
+criterion_main!(benches);
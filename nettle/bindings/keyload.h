@@ -0,0 +1,5 @@
+// SPDX-License-Identifier: Apache-2.0
+
+#include <nettle/asn1.h>
+#include <nettle/base64.h>
+#include <nettle/rsa.h>
@@ -0,0 +1,8 @@
+// SPDX-License-Identifier: Apache-2.0
+
+#include <nettle/aes.h>
+#include <nettle/cmac.h>
+#include <nettle/gcm.h>
+#include <nettle/hmac.h>
+#include <nettle/poly1305.h>
+#include <nettle/umac.h>
@@ -0,0 +1,9 @@
+// SPDX-License-Identifier: Apache-2.0
+
+#include <nettle/pbkdf2.h>
+#include <nettle/sha2.h>
+
+/* Balloon hashing was added in nettle 3.9. */
+#if __has_include(<nettle/balloon.h>)
+#include <nettle/balloon.h>
+#endif
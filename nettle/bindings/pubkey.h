@@ -0,0 +1,10 @@
+// SPDX-License-Identifier: Apache-2.0
+
+#include <nettle/bignum.h>
+#include <nettle/curve25519.h>
+#include <nettle/curve448.h>
+#include <nettle/ecc-curve.h>
+#include <nettle/ecdsa.h>
+#include <nettle/eddsa.h>
+#include <nettle/rsa.h>
+#include <nettle/sha2.h>
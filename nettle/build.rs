@@ -5,12 +5,33 @@ extern crate bindgen;
 use std::env;
 use std::path::PathBuf;
 
+// Functions that are not available in all supported nettle versions,
+// and the cfg flag to set when they are.
+const OPTIONAL_FUNCTIONS: &[(&str, &str)] = &[
+    ("nettle_balloon_sha256", "have_balloon"),
+    ("nettle_rsa_oaep_sha256_encrypt", "have_rsa_oaep"),
+];
+
+fn declares(code: &str, function: &str) -> bool {
+    code.match_indices(function).any(|(index, _)| {
+        !code[index + function.len()..].starts_with(|c: char| c == '_' || c.is_alphanumeric())
+    })
+}
+
 fn main() {
     println!("cargo:rustc-link-lib=nettle");
+    println!("cargo:rustc-link-lib=hogweed");
+    println!("cargo:rustc-link-lib=gmp");
 
     let out_path = PathBuf::from(env::var("OUT_DIR").unwrap());
 
-    for name in ["block", "aead"] {
+    for (_, cfg) in OPTIONAL_FUNCTIONS {
+        println!("cargo:rustc-check-cfg=cfg({})", cfg);
+    }
+
+    for name in [
+        "block", "aead", "mac", "kdf", "password", "pubkey", "bignum", "rng", "keyload",
+    ] {
         println!("cargo:rerun-if-changed=bindings/{}.h", name);
 
         let bindings = bindgen::Builder::default()
@@ -19,6 +40,13 @@ fn main() {
             .generate()
             .expect(&format!("Unable to generate {} bindings", name));
 
+        let code = bindings.to_string();
+        for (function, cfg) in OPTIONAL_FUNCTIONS {
+            if declares(&code, function) {
+                println!("cargo:rustc-cfg={}", cfg);
+            }
+        }
+
         bindings
             .write_to_file(out_path.join(format!("{}.rs", name)))
             .expect("Couldn't write bindings!");
@@ -19,7 +19,7 @@ impl Aes128GcmCtxBuilder {
         Self { iv: None }
     }
 
-    fn build(&mut self, key: &[u8]) -> Box<dyn Aead> {
+    fn build(&mut self, key: &[u8], for_encryption: bool) -> Box<dyn Aead> {
         let ctx = unsafe {
             let mut ctx: gcm_aes128_ctx = mem::zeroed();
             nettle_gcm_aes128_set_key(&mut ctx, key.as_ptr() as _);
@@ -27,7 +27,10 @@ impl Aes128GcmCtxBuilder {
             nettle_gcm_aes128_set_iv(&mut ctx, iv.len() as _, iv.as_ptr() as _);
             ctx
         };
-        Box::new(Aes128GcmCtx { ctx })
+        Box::new(Aes128GcmCtx {
+            ctx,
+            for_encryption,
+        })
     }
 }
 
@@ -38,16 +41,19 @@ impl AeadBuilder for Aes128GcmCtxBuilder {
     }
 
     fn for_encryption(&mut self, key: &[u8]) -> Box<dyn Aead> {
-        self.build(key)
+        self.build(key, true)
     }
 
     fn for_decryption(&mut self, key: &[u8]) -> Box<dyn Aead> {
-        self.build(key)
+        self.build(key, false)
     }
 }
 
 pub struct Aes128GcmCtx {
     ctx: gcm_aes128_ctx,
+    // GCM contexts work both ways in nettle, this only checks that
+    // seal is used as documented.
+    for_encryption: bool,
 }
 
 impl Aead for Aes128GcmCtx {
@@ -72,12 +78,33 @@ impl Aead for Aes128GcmCtx {
             );
         }
     }
+
+    fn set_nonce(&mut self, nonce: &[u8]) {
+        unsafe {
+            nettle_gcm_aes128_set_iv(&mut self.ctx, nonce.len() as _, nonce.as_ptr() as _);
+        }
+    }
+
+    fn seal(&mut self, nonce: &[u8], aad: &[u8], ptext: &[u8], ctext: &mut [u8], tag: &mut [u8]) {
+        debug_assert!(self.for_encryption, "seal on a decryption context");
+        unsafe {
+            nettle_gcm_aes128_set_iv(&mut self.ctx, nonce.len() as _, nonce.as_ptr() as _);
+            nettle_gcm_aes128_update(&mut self.ctx, aad.len() as _, aad.as_ptr() as _);
+            nettle_gcm_aes128_encrypt(
+                &mut self.ctx,
+                ctext.len() as _,
+                ctext.as_mut_ptr() as *mut _,
+                ptext.as_ptr() as _,
+            );
+            nettle_gcm_aes128_digest(&mut self.ctx, tag.len() as _, tag.as_mut_ptr() as _);
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use cipher_bench::AeadAlgorithm;
+    use cipher_bench::{check_seal, AeadAlgorithm};
     use rand::prelude::*;
     use std::convert::TryInto;
 
@@ -108,4 +135,9 @@ mod tests {
 
         assert_eq!(ptext, data_bytes);
     }
+
+    #[test]
+    fn seal_gcm_spec() {
+        check_seal(Aes128GcmCtxBuilder::new());
+    }
 }
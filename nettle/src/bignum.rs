@@ -0,0 +1,119 @@
+// SPDX-License-Identifier: Apache-2.0
+
+#![allow(non_upper_case_globals)]
+#![allow(non_camel_case_types)]
+#![allow(non_snake_case)]
+#![allow(dead_code)]
+
+include!(concat!(env!("OUT_DIR"), "/bignum.rs"));
+
+use cipher_bench::{Bignum, BignumBuilder};
+use std::mem;
+
+/// Nettle does its public-key arithmetic on GMP, so this benchmarks GMP
+/// directly.
+pub struct GmpCtxBuilder;
+
+impl GmpCtxBuilder {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+fn mpz_from_bytes(bytes: &[u8]) -> mpz_t {
+    unsafe {
+        let mut x: mpz_t = mem::zeroed();
+        __gmpz_init(x.as_mut_ptr());
+        nettle_mpz_set_str_256_u(x.as_mut_ptr(), bytes.len() as _, bytes.as_ptr());
+        x
+    }
+}
+
+impl BignumBuilder for GmpCtxBuilder {
+    fn for_operands(
+        &mut self,
+        base: &[u8],
+        multiplier: &[u8],
+        exponent: &[u8],
+        modulus: &[u8],
+    ) -> Box<dyn Bignum> {
+        Box::new(GmpCtx::new(base, multiplier, exponent, modulus))
+    }
+}
+
+pub struct GmpCtx {
+    base: mpz_t,
+    multiplier: mpz_t,
+    exponent: mpz_t,
+    modulus: mpz_t,
+    result: mpz_t,
+}
+
+impl GmpCtx {
+    fn new(base: &[u8], multiplier: &[u8], exponent: &[u8], modulus: &[u8]) -> Self {
+        Self {
+            base: mpz_from_bytes(base),
+            multiplier: mpz_from_bytes(multiplier),
+            exponent: mpz_from_bytes(exponent),
+            modulus: mpz_from_bytes(modulus),
+            result: mpz_from_bytes(&[]),
+        }
+    }
+}
+
+impl Bignum for GmpCtx {
+    fn mod_exp(&mut self) {
+        unsafe {
+            __gmpz_powm_sec(
+                self.result.as_mut_ptr(),
+                self.base.as_ptr(),
+                self.exponent.as_ptr(),
+                self.modulus.as_ptr(),
+            );
+        }
+    }
+
+    fn mod_mul(&mut self) {
+        unsafe {
+            __gmpz_mul(
+                self.result.as_mut_ptr(),
+                self.base.as_ptr(),
+                self.multiplier.as_ptr(),
+            );
+            __gmpz_tdiv_r(
+                self.result.as_mut_ptr(),
+                self.result.as_ptr(),
+                self.modulus.as_ptr(),
+            );
+        }
+    }
+}
+
+impl Drop for GmpCtx {
+    fn drop(&mut self) {
+        unsafe {
+            __gmpz_clear(self.base.as_mut_ptr());
+            __gmpz_clear(self.multiplier.as_mut_ptr());
+            __gmpz_clear(self.exponent.as_mut_ptr());
+            __gmpz_clear(self.modulus.as_mut_ptr());
+            __gmpz_clear(self.result.as_mut_ptr());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mod_exp_mod_mul() {
+        // 4^13 mod 497 = 445, and 4 * 6 mod 497 = 24.
+        let mut ctx = GmpCtx::new(&[4], &[6], &[13], &[0x01, 0xf1]);
+
+        ctx.mod_exp();
+        assert_eq!(unsafe { __gmpz_cmp_ui(ctx.result.as_ptr(), 445) }, 0);
+
+        ctx.mod_mul();
+        assert_eq!(unsafe { __gmpz_cmp_ui(ctx.result.as_ptr(), 24) }, 0);
+    }
+}
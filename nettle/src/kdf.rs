@@ -0,0 +1,110 @@
+// SPDX-License-Identifier: Apache-2.0
+
+#![allow(non_upper_case_globals)]
+#![allow(non_camel_case_types)]
+#![allow(non_snake_case)]
+#![allow(dead_code)]
+
+include!(concat!(env!("OUT_DIR"), "/kdf.rs"));
+
+use cipher_bench::{Kdf, KdfBuilder};
+use std::mem;
+use std::os::raw::c_void;
+
+macro_rules! hkdf {
+    ($builder:ident, $ctx:ident, $ctx_type:ty, $digest_size:expr, $set_key:ident, $update:ident, $digest:ident) => {
+        pub struct $builder;
+
+        impl $builder {
+            pub fn new() -> Self {
+                Self
+            }
+        }
+
+        impl KdfBuilder for $builder {
+            fn for_derivation(&mut self) -> Box<dyn Kdf> {
+                let ctx: $ctx_type = unsafe { mem::zeroed() };
+                Box::new($ctx { ctx })
+            }
+        }
+
+        pub struct $ctx {
+            ctx: $ctx_type,
+        }
+
+        impl Kdf for $ctx {
+            fn extract(&mut self, salt: &[u8], ikm: &[u8], prk: &mut [u8]) {
+                unsafe {
+                    let update: extern "C" fn(*mut c_void, u64, *const u8) =
+                        mem::transmute($update as *const c_void);
+                    let digest: extern "C" fn(*mut c_void, u64, *mut u8) =
+                        mem::transmute($digest as *const c_void);
+
+                    $set_key(&mut self.ctx, salt.len() as _, salt.as_ptr() as _);
+                    nettle_hkdf_extract(
+                        (&mut self.ctx as *mut $ctx_type) as *mut c_void,
+                        Some(update),
+                        Some(digest),
+                        $digest_size as _,
+                        ikm.len() as _,
+                        ikm.as_ptr() as _,
+                        prk.as_mut_ptr() as _,
+                    );
+                }
+            }
+
+            fn expand(&mut self, prk: &[u8], info: &[u8], okm: &mut [u8]) {
+                unsafe {
+                    let update: extern "C" fn(*mut c_void, u64, *const u8) =
+                        mem::transmute($update as *const c_void);
+                    let digest: extern "C" fn(*mut c_void, u64, *mut u8) =
+                        mem::transmute($digest as *const c_void);
+
+                    $set_key(&mut self.ctx, prk.len() as _, prk.as_ptr() as _);
+                    nettle_hkdf_expand(
+                        (&mut self.ctx as *mut $ctx_type) as *mut c_void,
+                        Some(update),
+                        Some(digest),
+                        $digest_size as _,
+                        info.len() as _,
+                        info.as_ptr() as _,
+                        okm.len() as _,
+                        okm.as_mut_ptr() as _,
+                    );
+                }
+            }
+        }
+    };
+}
+
+hkdf!(
+    HkdfSha256CtxBuilder,
+    HkdfSha256Ctx,
+    hmac_sha256_ctx,
+    SHA256_DIGEST_SIZE,
+    nettle_hmac_sha256_set_key,
+    nettle_hmac_sha256_update,
+    nettle_hmac_sha256_digest
+);
+
+// HMAC-SHA384 shares its context and update function with HMAC-SHA512.
+hkdf!(
+    HkdfSha384CtxBuilder,
+    HkdfSha384Ctx,
+    hmac_sha512_ctx,
+    SHA384_DIGEST_SIZE,
+    nettle_hmac_sha384_set_key,
+    nettle_hmac_sha512_update,
+    nettle_hmac_sha384_digest
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cipher_bench::check_tls13_rfc8448;
+
+    #[test]
+    fn tls13_rfc8448() {
+        check_tls13_rfc8448(HkdfSha256CtxBuilder::new());
+    }
+}
@@ -0,0 +1,219 @@
+// SPDX-License-Identifier: Apache-2.0
+
+#![allow(non_upper_case_globals)]
+#![allow(non_camel_case_types)]
+#![allow(non_snake_case)]
+#![allow(dead_code)]
+
+include!(concat!(env!("OUT_DIR"), "/keyload.rs"));
+
+use cipher_bench::{KeyAlgorithm, KeyDecoder, KeyDecoderBuilder, KeyEncoding, KeyFormat};
+use std::mem;
+
+/// Decodes RSA keys with rsa_keypair_from_der and the asn1_der_*
+/// iterator. nettle has no parser for EC keys nor for encrypted PKCS#8.
+pub struct RsaKeyDecoderCtxBuilder;
+
+impl RsaKeyDecoderCtxBuilder {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl KeyDecoderBuilder for RsaKeyDecoderCtxBuilder {
+    fn for_decoding(
+        &mut self,
+        algorithm: &KeyAlgorithm,
+        format: &KeyFormat,
+        encoding: &KeyEncoding,
+    ) -> Box<dyn KeyDecoder> {
+        assert!(matches!(algorithm, KeyAlgorithm::Rsa2048));
+        assert!(!matches!(format, KeyFormat::EncryptedPkcs8));
+        Box::new(RsaKeyDecoderCtx {
+            format: *format,
+            encoding: *encoding,
+            der: Vec::new(),
+        })
+    }
+}
+
+pub struct RsaKeyDecoderCtx {
+    format: KeyFormat,
+    encoding: KeyEncoding,
+    der: Vec<u8>,
+}
+
+/// Decodes the base64 body of a PEM document into `der`, ignoring the
+/// label.
+fn pem_decode(encoded: &[u8], der: &mut Vec<u8>) -> bool {
+    let begin = match encoded.iter().position(|&c| c == b'\n') {
+        Some(index) => index + 1,
+        None => return false,
+    };
+    let end = match encoded[begin..].iter().position(|&c| c == b'-') {
+        Some(index) => begin + index,
+        None => return false,
+    };
+    let body = &encoded[begin..end];
+
+    der.resize((body.len() + 1) * 6 / 8, 0);
+    unsafe {
+        let mut ctx: base64_decode_ctx = mem::zeroed();
+        nettle_base64_decode_init(&mut ctx);
+        let mut len = der.len();
+        if nettle_base64_decode_update(
+            &mut ctx,
+            &mut len,
+            der.as_mut_ptr(),
+            body.len(),
+            body.as_ptr() as _,
+        ) != 1
+            || nettle_base64_decode_final(&mut ctx) != 1
+        {
+            return false;
+        }
+        der.truncate(len);
+    }
+    true
+}
+
+/// Decodes a PKCS#1 RSAPrivateKey.
+fn rsa_private_key_decode(der: &[u8]) -> bool {
+    unsafe {
+        let mut public: rsa_public_key = mem::zeroed();
+        let mut private: rsa_private_key = mem::zeroed();
+        nettle_rsa_public_key_init(&mut public);
+        nettle_rsa_private_key_init(&mut private);
+        let ret =
+            nettle_rsa_keypair_from_der(&mut public, &mut private, 0, der.len(), der.as_ptr());
+        nettle_rsa_public_key_clear(&mut public);
+        nettle_rsa_private_key_clear(&mut private);
+        ret == 1
+    }
+}
+
+/// Decodes a PKCS#8 PrivateKeyInfo, leaving the algorithm identifier to
+/// the RSAPrivateKey parser.
+fn pkcs8_decode(der: &[u8]) -> bool {
+    unsafe {
+        let mut i: asn1_der_iterator = mem::zeroed();
+        if nettle_asn1_der_iterator_first(&mut i, der.len(), der.as_ptr())
+            != asn1_iterator_result_ASN1_ITERATOR_CONSTRUCTED
+            || i.type_ != asn1_type_ASN1_SEQUENCE
+        {
+            return false;
+        }
+        // version
+        if nettle_asn1_der_decode_constructed_last(&mut i)
+            != asn1_iterator_result_ASN1_ITERATOR_PRIMITIVE
+            || i.type_ != asn1_type_ASN1_INTEGER
+        {
+            return false;
+        }
+        // privateKeyAlgorithm
+        if nettle_asn1_der_iterator_next(&mut i) != asn1_iterator_result_ASN1_ITERATOR_CONSTRUCTED
+            || i.type_ != asn1_type_ASN1_SEQUENCE
+        {
+            return false;
+        }
+        // privateKey
+        if nettle_asn1_der_iterator_next(&mut i) != asn1_iterator_result_ASN1_ITERATOR_PRIMITIVE
+            || i.type_ != asn1_type_ASN1_OCTETSTRING
+        {
+            return false;
+        }
+        rsa_private_key_decode(std::slice::from_raw_parts(i.data, i.length))
+    }
+}
+
+/// Decodes a SubjectPublicKeyInfo holding a PKCS#1 RSAPublicKey.
+fn spki_decode(der: &[u8]) -> bool {
+    unsafe {
+        let mut i: asn1_der_iterator = mem::zeroed();
+        if nettle_asn1_der_iterator_first(&mut i, der.len(), der.as_ptr())
+            != asn1_iterator_result_ASN1_ITERATOR_CONSTRUCTED
+            || i.type_ != asn1_type_ASN1_SEQUENCE
+        {
+            return false;
+        }
+        // algorithm
+        if nettle_asn1_der_decode_constructed_last(&mut i)
+            != asn1_iterator_result_ASN1_ITERATOR_CONSTRUCTED
+            || i.type_ != asn1_type_ASN1_SEQUENCE
+        {
+            return false;
+        }
+        // subjectPublicKey
+        if nettle_asn1_der_iterator_next(&mut i) != asn1_iterator_result_ASN1_ITERATOR_PRIMITIVE
+            || i.type_ != asn1_type_ASN1_BITSTRING
+        {
+            return false;
+        }
+        if nettle_asn1_der_decode_bitstring_last(&mut i)
+            != asn1_iterator_result_ASN1_ITERATOR_CONSTRUCTED
+        {
+            return false;
+        }
+
+        let mut public: rsa_public_key = mem::zeroed();
+        nettle_rsa_public_key_init(&mut public);
+        let ret = nettle_rsa_public_key_from_der_iterator(&mut public, 0, &mut i);
+        nettle_rsa_public_key_clear(&mut public);
+        ret == 1
+    }
+}
+
+impl KeyDecoder for RsaKeyDecoderCtx {
+    fn decode(&mut self, encoded: &[u8]) -> bool {
+        let der = match self.encoding {
+            KeyEncoding::Der => encoded,
+            KeyEncoding::Pem => {
+                if !pem_decode(encoded, &mut self.der) {
+                    return false;
+                }
+                &self.der
+            }
+        };
+        match self.format {
+            KeyFormat::TypeSpecific => rsa_private_key_decode(der),
+            KeyFormat::Pkcs8 => pkcs8_decode(der),
+            KeyFormat::SubjectPublicKeyInfo => spki_decode(der),
+            KeyFormat::EncryptedPkcs8 => unreachable!(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cipher_bench::key_fixture;
+
+    #[test]
+    fn decode_rsa_fixtures() {
+        for format in [
+            KeyFormat::TypeSpecific,
+            KeyFormat::Pkcs8,
+            KeyFormat::SubjectPublicKeyInfo,
+        ] {
+            for encoding in [KeyEncoding::Der, KeyEncoding::Pem] {
+                let encoded = key_fixture(&KeyAlgorithm::Rsa2048, &format, &encoding).unwrap();
+
+                let mut builder = RsaKeyDecoderCtxBuilder::new();
+                let mut ctx = builder.for_decoding(&KeyAlgorithm::Rsa2048, &format, &encoding);
+                assert!(ctx.decode(encoded));
+            }
+        }
+
+        // A public key is not a private key.
+        let encoded = key_fixture(
+            &KeyAlgorithm::Rsa2048,
+            &KeyFormat::SubjectPublicKeyInfo,
+            &KeyEncoding::Der,
+        )
+        .unwrap();
+        let mut builder = RsaKeyDecoderCtxBuilder::new();
+        let mut ctx =
+            builder.for_decoding(&KeyAlgorithm::Rsa2048, &KeyFormat::Pkcs8, &KeyEncoding::Der);
+        assert!(!ctx.decode(encoded));
+    }
+}
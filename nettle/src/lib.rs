@@ -5,3 +5,24 @@ pub use block::*;
 
 mod aead;
 pub use aead::*;
+
+mod mac;
+pub use mac::*;
+
+mod kdf;
+pub use kdf::*;
+
+mod password;
+pub use password::*;
+
+mod pubkey;
+pub use pubkey::*;
+
+mod bignum;
+pub use bignum::*;
+
+mod rng;
+pub use rng::*;
+
+mod keyload;
+pub use keyload::*;
@@ -0,0 +1,298 @@
+// SPDX-License-Identifier: Apache-2.0
+
+#![allow(non_upper_case_globals)]
+#![allow(non_camel_case_types)]
+#![allow(non_snake_case)]
+#![allow(dead_code)]
+
+include!(concat!(env!("OUT_DIR"), "/mac.rs"));
+
+use cipher_bench::{increment_nonce, Mac, MacBuilder};
+use std::mem;
+
+pub struct Aes128CmacCtxBuilder;
+
+impl Aes128CmacCtxBuilder {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl MacBuilder for Aes128CmacCtxBuilder {
+    fn nonce(&mut self, _nonce: &[u8]) -> &mut Self {
+        self
+    }
+
+    fn for_key(&mut self, key: &[u8]) -> Box<dyn Mac> {
+        let ctx = unsafe {
+            let mut ctx: cmac_aes128_ctx = mem::zeroed();
+            nettle_cmac_aes128_set_key(&mut ctx, key.as_ptr() as _);
+            ctx
+        };
+        Box::new(Aes128CmacCtx { ctx })
+    }
+}
+
+pub struct Aes128CmacCtx {
+    ctx: cmac_aes128_ctx,
+}
+
+impl Mac for Aes128CmacCtx {
+    fn update(&mut self, data: &[u8]) {
+        unsafe {
+            nettle_cmac_aes128_update(&mut self.ctx, data.len() as _, data.as_ptr() as _);
+        }
+    }
+
+    fn digest(&mut self, digest: &mut [u8]) {
+        unsafe {
+            nettle_cmac_aes128_digest(&mut self.ctx, digest.len() as _, digest.as_mut_ptr() as _);
+        }
+    }
+}
+
+/// GMAC is GCM with an empty plaintext, where all input is fed as
+/// associated data. The IV is incremented for every message.
+pub struct Aes128GmacCtxBuilder {
+    iv: Option<Vec<u8>>,
+}
+
+impl Aes128GmacCtxBuilder {
+    pub fn new() -> Self {
+        Self { iv: None }
+    }
+}
+
+impl MacBuilder for Aes128GmacCtxBuilder {
+    fn nonce(&mut self, iv: &[u8]) -> &mut Self {
+        self.iv.replace(iv.to_vec());
+        self
+    }
+
+    fn for_key(&mut self, key: &[u8]) -> Box<dyn Mac> {
+        let iv = self.iv.take().unwrap();
+        let ctx = unsafe {
+            let mut ctx: gcm_aes128_ctx = mem::zeroed();
+            nettle_gcm_aes128_set_key(&mut ctx, key.as_ptr() as _);
+            nettle_gcm_aes128_set_iv(&mut ctx, iv.len() as _, iv.as_ptr() as _);
+            ctx
+        };
+        Box::new(Aes128GmacCtx { ctx, iv })
+    }
+}
+
+pub struct Aes128GmacCtx {
+    ctx: gcm_aes128_ctx,
+    iv: Vec<u8>,
+}
+
+impl Mac for Aes128GmacCtx {
+    fn update(&mut self, data: &[u8]) {
+        unsafe {
+            nettle_gcm_aes128_update(&mut self.ctx, data.len() as _, data.as_ptr() as _);
+        }
+    }
+
+    fn digest(&mut self, digest: &mut [u8]) {
+        unsafe {
+            nettle_gcm_aes128_digest(&mut self.ctx, digest.len() as _, digest.as_mut_ptr() as _);
+            increment_nonce(&mut self.iv);
+            nettle_gcm_aes128_set_iv(&mut self.ctx, self.iv.len() as _, self.iv.as_ptr() as _);
+        }
+    }
+}
+
+pub struct HmacSha256CtxBuilder;
+
+impl HmacSha256CtxBuilder {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl MacBuilder for HmacSha256CtxBuilder {
+    fn nonce(&mut self, _nonce: &[u8]) -> &mut Self {
+        self
+    }
+
+    fn for_key(&mut self, key: &[u8]) -> Box<dyn Mac> {
+        let ctx = unsafe {
+            let mut ctx: hmac_sha256_ctx = mem::zeroed();
+            nettle_hmac_sha256_set_key(&mut ctx, key.len() as _, key.as_ptr() as _);
+            ctx
+        };
+        Box::new(HmacSha256Ctx { ctx })
+    }
+}
+
+pub struct HmacSha256Ctx {
+    ctx: hmac_sha256_ctx,
+}
+
+impl Mac for HmacSha256Ctx {
+    fn update(&mut self, data: &[u8]) {
+        unsafe {
+            nettle_hmac_sha256_update(&mut self.ctx, data.len() as _, data.as_ptr() as _);
+        }
+    }
+
+    fn digest(&mut self, digest: &mut [u8]) {
+        unsafe {
+            nettle_hmac_sha256_digest(&mut self.ctx, digest.len() as _, digest.as_mut_ptr() as _);
+        }
+    }
+}
+
+/// Nettle only exposes Poly1305 in its Poly1305-AES form, where the
+/// first half of the key is used to encrypt the nonce, which is
+/// incremented for every message.
+pub struct Poly1305AesCtxBuilder {
+    nonce: Option<Vec<u8>>,
+}
+
+impl Poly1305AesCtxBuilder {
+    pub fn new() -> Self {
+        Self { nonce: None }
+    }
+}
+
+impl MacBuilder for Poly1305AesCtxBuilder {
+    fn nonce(&mut self, nonce: &[u8]) -> &mut Self {
+        self.nonce.replace(nonce.to_vec());
+        self
+    }
+
+    fn for_key(&mut self, key: &[u8]) -> Box<dyn Mac> {
+        let ctx = unsafe {
+            let mut ctx: poly1305_aes_ctx = mem::zeroed();
+            nettle_poly1305_aes_set_key(&mut ctx, key.as_ptr() as _);
+            let nonce = self.nonce.take().unwrap();
+            nettle_poly1305_aes_set_nonce(&mut ctx, nonce.as_ptr() as _);
+            ctx
+        };
+        Box::new(Poly1305AesCtx { ctx })
+    }
+}
+
+pub struct Poly1305AesCtx {
+    ctx: poly1305_aes_ctx,
+}
+
+impl Mac for Poly1305AesCtx {
+    fn update(&mut self, data: &[u8]) {
+        unsafe {
+            nettle_poly1305_aes_update(&mut self.ctx, data.len() as _, data.as_ptr() as _);
+        }
+    }
+
+    fn digest(&mut self, digest: &mut [u8]) {
+        unsafe {
+            nettle_poly1305_aes_digest(&mut self.ctx, digest.len() as _, digest.as_mut_ptr() as _);
+        }
+    }
+}
+
+macro_rules! umac {
+    ($builder:ident, $ctx:ident, $ctx_type:ty, $set_key:ident, $set_nonce:ident, $update:ident, $digest:ident) => {
+        pub struct $builder {
+            nonce: Option<Vec<u8>>,
+        }
+
+        impl $builder {
+            pub fn new() -> Self {
+                Self { nonce: None }
+            }
+        }
+
+        impl MacBuilder for $builder {
+            fn nonce(&mut self, nonce: &[u8]) -> &mut Self {
+                self.nonce.replace(nonce.to_vec());
+                self
+            }
+
+            fn for_key(&mut self, key: &[u8]) -> Box<dyn Mac> {
+                let ctx = unsafe {
+                    let mut ctx: $ctx_type = mem::zeroed();
+                    $set_key(&mut ctx, key.as_ptr() as _);
+                    let nonce = self.nonce.take().unwrap();
+                    $set_nonce(&mut ctx, nonce.len() as _, nonce.as_ptr() as _);
+                    ctx
+                };
+                Box::new($ctx { ctx })
+            }
+        }
+
+        pub struct $ctx {
+            ctx: $ctx_type,
+        }
+
+        impl Mac for $ctx {
+            fn update(&mut self, data: &[u8]) {
+                unsafe {
+                    $update(&mut self.ctx, data.len() as _, data.as_ptr() as _);
+                }
+            }
+
+            fn digest(&mut self, digest: &mut [u8]) {
+                unsafe {
+                    $digest(&mut self.ctx, digest.len() as _, digest.as_mut_ptr() as _);
+                }
+            }
+        }
+    };
+}
+
+umac!(
+    Umac32CtxBuilder,
+    Umac32Ctx,
+    umac32_ctx,
+    nettle_umac32_set_key,
+    nettle_umac32_set_nonce,
+    nettle_umac32_update,
+    nettle_umac32_digest
+);
+umac!(
+    Umac64CtxBuilder,
+    Umac64Ctx,
+    umac64_ctx,
+    nettle_umac64_set_key,
+    nettle_umac64_set_nonce,
+    nettle_umac64_update,
+    nettle_umac64_digest
+);
+umac!(
+    Umac96CtxBuilder,
+    Umac96Ctx,
+    umac96_ctx,
+    nettle_umac96_set_key,
+    nettle_umac96_set_nonce,
+    nettle_umac96_update,
+    nettle_umac96_digest
+);
+umac!(
+    Umac128CtxBuilder,
+    Umac128Ctx,
+    umac128_ctx,
+    nettle_umac128_set_key,
+    nettle_umac128_set_nonce,
+    nettle_umac128_update,
+    nettle_umac128_digest
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cipher_bench::{check_mac, MacAlgorithm};
+
+    #[test]
+    fn known_answers() {
+        check_mac(MacAlgorithm::Aes128Cmac, Aes128CmacCtxBuilder::new());
+        check_mac(MacAlgorithm::Aes128Gmac, Aes128GmacCtxBuilder::new());
+        check_mac(MacAlgorithm::HmacSha256, HmacSha256CtxBuilder::new());
+        check_mac(MacAlgorithm::Poly1305Aes, Poly1305AesCtxBuilder::new());
+        check_mac(MacAlgorithm::Umac32, Umac32CtxBuilder::new());
+        check_mac(MacAlgorithm::Umac64, Umac64CtxBuilder::new());
+        check_mac(MacAlgorithm::Umac96, Umac96CtxBuilder::new());
+        check_mac(MacAlgorithm::Umac128, Umac128CtxBuilder::new());
+    }
+}
@@ -0,0 +1,184 @@
+// SPDX-License-Identifier: Apache-2.0
+
+#![allow(non_upper_case_globals)]
+#![allow(non_camel_case_types)]
+#![allow(non_snake_case)]
+#![allow(dead_code)]
+
+include!(concat!(env!("OUT_DIR"), "/password.rs"));
+
+use cipher_bench::{PasswordHash, PasswordHashBuilder, PasswordHashCost};
+
+pub struct Pbkdf2Sha256CtxBuilder {
+    cost: Option<PasswordHashCost>,
+}
+
+impl Pbkdf2Sha256CtxBuilder {
+    pub fn new() -> Self {
+        Self { cost: None }
+    }
+}
+
+impl PasswordHashBuilder for Pbkdf2Sha256CtxBuilder {
+    fn cost(&mut self, cost: &PasswordHashCost) -> &mut Self {
+        self.cost.replace(*cost);
+        self
+    }
+
+    fn for_derivation(&mut self) -> Box<dyn PasswordHash> {
+        Box::new(Pbkdf2Sha256Ctx {
+            iterations: self.cost.take().unwrap().time,
+        })
+    }
+}
+
+pub struct Pbkdf2Sha256Ctx {
+    iterations: u32,
+}
+
+impl PasswordHash for Pbkdf2Sha256Ctx {
+    fn derive(&mut self, password: &[u8], salt: &[u8], key: &mut [u8]) {
+        unsafe {
+            nettle_pbkdf2_hmac_sha256(
+                password.len() as _,
+                password.as_ptr() as _,
+                self.iterations as _,
+                salt.len() as _,
+                salt.as_ptr() as _,
+                key.len() as _,
+                key.as_mut_ptr() as _,
+            );
+        }
+    }
+}
+
+pub struct Pbkdf2Sha512CtxBuilder {
+    cost: Option<PasswordHashCost>,
+}
+
+impl Pbkdf2Sha512CtxBuilder {
+    pub fn new() -> Self {
+        Self { cost: None }
+    }
+}
+
+impl PasswordHashBuilder for Pbkdf2Sha512CtxBuilder {
+    fn cost(&mut self, cost: &PasswordHashCost) -> &mut Self {
+        self.cost.replace(*cost);
+        self
+    }
+
+    fn for_derivation(&mut self) -> Box<dyn PasswordHash> {
+        Box::new(Pbkdf2Sha512Ctx {
+            iterations: self.cost.take().unwrap().time,
+        })
+    }
+}
+
+pub struct Pbkdf2Sha512Ctx {
+    iterations: u32,
+}
+
+impl PasswordHash for Pbkdf2Sha512Ctx {
+    fn derive(&mut self, password: &[u8], salt: &[u8], key: &mut [u8]) {
+        unsafe {
+            nettle_pbkdf2_hmac_sha512(
+                password.len() as _,
+                password.as_ptr() as _,
+                self.iterations as _,
+                salt.len() as _,
+                salt.as_ptr() as _,
+                key.len() as _,
+                key.as_mut_ptr() as _,
+            );
+        }
+    }
+}
+
+#[cfg(have_balloon)]
+pub struct BalloonSha256CtxBuilder {
+    cost: Option<PasswordHashCost>,
+}
+
+#[cfg(have_balloon)]
+impl BalloonSha256CtxBuilder {
+    pub fn new() -> Self {
+        Self { cost: None }
+    }
+}
+
+#[cfg(have_balloon)]
+impl PasswordHashBuilder for BalloonSha256CtxBuilder {
+    fn cost(&mut self, cost: &PasswordHashCost) -> &mut Self {
+        self.cost.replace(*cost);
+        self
+    }
+
+    fn for_derivation(&mut self) -> Box<dyn PasswordHash> {
+        let cost = self.cost.take().unwrap();
+        let scratch_len = unsafe { nettle_balloon_itch(SHA256_DIGEST_SIZE as _, cost.memory as _) };
+        Box::new(BalloonSha256Ctx {
+            s_cost: cost.memory,
+            t_cost: cost.time,
+            scratch: vec![0u8; scratch_len as usize],
+        })
+    }
+}
+
+/// Balloon hashing always produces a single SHA-256 digest, so the
+/// derived key must be `SHA256_DIGEST_SIZE` bytes long.
+#[cfg(have_balloon)]
+pub struct BalloonSha256Ctx {
+    s_cost: u32,
+    t_cost: u32,
+    scratch: Vec<u8>,
+}
+
+#[cfg(have_balloon)]
+impl PasswordHash for BalloonSha256Ctx {
+    fn derive(&mut self, password: &[u8], salt: &[u8], key: &mut [u8]) {
+        assert_eq!(key.len(), SHA256_DIGEST_SIZE as usize);
+        unsafe {
+            nettle_balloon_sha256(
+                self.s_cost as _,
+                self.t_cost as _,
+                password.len() as _,
+                password.as_ptr() as _,
+                salt.len() as _,
+                salt.as_ptr() as _,
+                self.scratch.as_mut_ptr() as _,
+                key.as_mut_ptr() as _,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // RFC 7914, section 11.
+    #[test]
+    fn pbkdf2_rfc7914() {
+        let expected = [
+            0x55, 0xac, 0x04, 0x6e, 0x56, 0xe3, 0x08, 0x9f, 0xec, 0x16, 0x91, 0xc2, 0x25, 0x44,
+            0xb6, 0x05, 0xf9, 0x41, 0x85, 0x21, 0x6d, 0xde, 0x04, 0x65, 0xe6, 0x8b, 0x9d, 0x57,
+            0xc2, 0x0d, 0xac, 0xbc,
+        ];
+
+        let mut key = vec![0u8; 32];
+
+        let mut builder = Pbkdf2Sha256CtxBuilder::new();
+
+        let mut ctx = builder
+            .cost(&PasswordHashCost {
+                time: 1,
+                memory: 0,
+                parallelism: 0,
+            })
+            .for_derivation();
+        ctx.derive(b"passwd", b"salt", &mut key);
+
+        assert_eq!(key, expected);
+    }
+}
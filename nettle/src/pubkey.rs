@@ -0,0 +1,1031 @@
+// SPDX-License-Identifier: Apache-2.0
+
+#![allow(non_upper_case_globals)]
+#![allow(non_camel_case_types)]
+#![allow(non_snake_case)]
+#![allow(dead_code)]
+
+include!(concat!(env!("OUT_DIR"), "/pubkey.rs"));
+
+#[cfg(have_rsa_oaep)]
+use cipher_bench::{AsymmetricCipher, AsymmetricCipherBuilder};
+use cipher_bench::{KeyAgreement, KeyAgreementBuilder, Signature, SignatureBuilder};
+use cipher_bench::{ScalarMul, ScalarMulBuilder};
+use rand::prelude::*;
+use std::mem;
+use std::os::raw::c_void;
+use std::ptr;
+use std::slice;
+
+unsafe extern "C" fn random(_ctx: *mut c_void, length: size_t, dst: *mut u8) {
+    rand::thread_rng().fill(slice::from_raw_parts_mut(dst, length as usize));
+}
+
+struct Mpz(mpz_t);
+
+impl Mpz {
+    fn new() -> Self {
+        unsafe {
+            let mut x: mpz_t = mem::zeroed();
+            __gmpz_init(x.as_mut_ptr());
+            Self(x)
+        }
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Self {
+        let mut x = Self::new();
+        unsafe {
+            nettle_mpz_set_str_256_u(x.0.as_mut_ptr(), bytes.len() as _, bytes.as_ptr());
+        }
+        x
+    }
+
+    fn from_hex(hex: &str) -> Self {
+        let mut x = Self::new();
+        unsafe {
+            __gmpz_set_str(x.0.as_mut_ptr(), hex.as_ptr() as _, 16);
+        }
+        x
+    }
+
+    fn to_bytes(&self, len: usize) -> Vec<u8> {
+        let mut bytes = vec![0u8; len];
+        unsafe {
+            nettle_mpz_get_str_256(len as _, bytes.as_mut_ptr(), self.0.as_ptr());
+        }
+        bytes
+    }
+}
+
+impl Drop for Mpz {
+    fn drop(&mut self) {
+        unsafe {
+            __gmpz_clear(self.0.as_mut_ptr());
+        }
+    }
+}
+
+struct RsaKeyPair {
+    public: rsa_public_key,
+    private: rsa_private_key,
+}
+
+impl RsaKeyPair {
+    fn generate(bits: u32) -> Self {
+        unsafe {
+            let mut public: rsa_public_key = mem::zeroed();
+            let mut private: rsa_private_key = mem::zeroed();
+            nettle_rsa_public_key_init(&mut public);
+            nettle_rsa_private_key_init(&mut private);
+            __gmpz_set_ui(public.e.as_mut_ptr(), 65537);
+            let _ = nettle_rsa_generate_keypair(
+                &mut public,
+                &mut private,
+                ptr::null_mut(),
+                Some(random),
+                ptr::null_mut(),
+                None,
+                bits,
+                0,
+            );
+            Self { public, private }
+        }
+    }
+}
+
+impl Drop for RsaKeyPair {
+    fn drop(&mut self) {
+        unsafe {
+            nettle_rsa_public_key_clear(&mut self.public);
+            nettle_rsa_private_key_clear(&mut self.private);
+        }
+    }
+}
+
+pub struct RsaPkcs1Sha256CtxBuilder {
+    bits: u32,
+}
+
+impl RsaPkcs1Sha256CtxBuilder {
+    pub fn new(bits: u32) -> Self {
+        Self { bits }
+    }
+}
+
+impl SignatureBuilder for RsaPkcs1Sha256CtxBuilder {
+    fn generate(&mut self) -> Box<dyn Signature> {
+        Box::new(RsaPkcs1Sha256Ctx {
+            key: RsaKeyPair::generate(self.bits),
+        })
+    }
+}
+
+pub struct RsaPkcs1Sha256Ctx {
+    key: RsaKeyPair,
+}
+
+impl Signature for RsaPkcs1Sha256Ctx {
+    fn sign(&mut self, msg: &[u8]) -> Vec<u8> {
+        let mut s = Mpz::new();
+        unsafe {
+            let mut hash: sha256_ctx = mem::zeroed();
+            nettle_sha256_init(&mut hash);
+            nettle_sha256_update(&mut hash, msg.len() as _, msg.as_ptr());
+            nettle_rsa_sha256_sign_tr(
+                &self.key.public,
+                &self.key.private,
+                ptr::null_mut(),
+                Some(random),
+                &mut hash,
+                s.0.as_mut_ptr(),
+            );
+        }
+        s.to_bytes(self.key.public.size as usize)
+    }
+
+    fn verify(&mut self, msg: &[u8], sig: &[u8]) -> bool {
+        let s = Mpz::from_bytes(sig);
+        unsafe {
+            let mut hash: sha256_ctx = mem::zeroed();
+            nettle_sha256_init(&mut hash);
+            nettle_sha256_update(&mut hash, msg.len() as _, msg.as_ptr());
+            nettle_rsa_sha256_verify(&self.key.public, &mut hash, s.0.as_ptr()) != 0
+        }
+    }
+}
+
+pub struct RsaPssSha256CtxBuilder {
+    bits: u32,
+}
+
+impl RsaPssSha256CtxBuilder {
+    pub fn new(bits: u32) -> Self {
+        Self { bits }
+    }
+}
+
+impl SignatureBuilder for RsaPssSha256CtxBuilder {
+    fn generate(&mut self) -> Box<dyn Signature> {
+        Box::new(RsaPssSha256Ctx {
+            key: RsaKeyPair::generate(self.bits),
+        })
+    }
+}
+
+/// RSA-PSS with a salt as long as the SHA-256 digest.
+pub struct RsaPssSha256Ctx {
+    key: RsaKeyPair,
+}
+
+impl RsaPssSha256Ctx {
+    fn digest(msg: &[u8]) -> [u8; SHA256_DIGEST_SIZE as usize] {
+        let mut digest = [0u8; SHA256_DIGEST_SIZE as usize];
+        unsafe {
+            let mut hash: sha256_ctx = mem::zeroed();
+            nettle_sha256_init(&mut hash);
+            nettle_sha256_update(&mut hash, msg.len() as _, msg.as_ptr());
+            nettle_sha256_digest(&mut hash, digest.len() as _, digest.as_mut_ptr());
+        }
+        digest
+    }
+}
+
+impl Signature for RsaPssSha256Ctx {
+    fn sign(&mut self, msg: &[u8]) -> Vec<u8> {
+        let digest = Self::digest(msg);
+        let mut salt = [0u8; SHA256_DIGEST_SIZE as usize];
+        rand::thread_rng().fill(&mut salt);
+        let mut s = Mpz::new();
+        unsafe {
+            nettle_rsa_pss_sha256_sign_digest_tr(
+                &self.key.public,
+                &self.key.private,
+                ptr::null_mut(),
+                Some(random),
+                salt.len() as _,
+                salt.as_ptr(),
+                digest.as_ptr(),
+                s.0.as_mut_ptr(),
+            );
+        }
+        s.to_bytes(self.key.public.size as usize)
+    }
+
+    fn verify(&mut self, msg: &[u8], sig: &[u8]) -> bool {
+        let digest = Self::digest(msg);
+        let s = Mpz::from_bytes(sig);
+        unsafe {
+            nettle_rsa_pss_sha256_verify_digest(
+                &self.key.public,
+                SHA256_DIGEST_SIZE as _,
+                digest.as_ptr(),
+                s.0.as_ptr(),
+            ) != 0
+        }
+    }
+}
+
+struct EccKeyPair {
+    public: ecc_point,
+    private: ecc_scalar,
+}
+
+impl EccKeyPair {
+    fn generate(curve: *const ecc_curve) -> Self {
+        unsafe {
+            let mut public: ecc_point = mem::zeroed();
+            let mut private: ecc_scalar = mem::zeroed();
+            nettle_ecc_point_init(&mut public, curve);
+            nettle_ecc_scalar_init(&mut private, curve);
+            nettle_ecdsa_generate_keypair(&mut public, &mut private, ptr::null_mut(), Some(random));
+            Self { public, private }
+        }
+    }
+}
+
+impl Drop for EccKeyPair {
+    fn drop(&mut self) {
+        unsafe {
+            nettle_ecc_point_clear(&mut self.public);
+            nettle_ecc_scalar_clear(&mut self.private);
+        }
+    }
+}
+
+macro_rules! ecdsa {
+    ($builder:ident, $ctx:ident, $curve:ident, $hash_ctx:ty, $digest_size:expr, $init:ident, $update:ident, $digest:ident) => {
+        pub struct $builder;
+
+        impl $builder {
+            pub fn new() -> Self {
+                Self
+            }
+        }
+
+        impl SignatureBuilder for $builder {
+            fn generate(&mut self) -> Box<dyn Signature> {
+                let curve = unsafe { $curve() };
+                Box::new($ctx {
+                    key: EccKeyPair::generate(curve),
+                    len: unsafe { (nettle_ecc_bit_size(curve) as usize).div_ceil(8) },
+                })
+            }
+        }
+
+        /// Signatures are encoded as the fixed-width concatenation of r and s.
+        pub struct $ctx {
+            key: EccKeyPair,
+            len: usize,
+        }
+
+        impl $ctx {
+            fn digest(msg: &[u8]) -> [u8; $digest_size as usize] {
+                let mut digest = [0u8; $digest_size as usize];
+                unsafe {
+                    let mut hash: $hash_ctx = mem::zeroed();
+                    $init(&mut hash);
+                    $update(&mut hash, msg.len() as _, msg.as_ptr());
+                    $digest(&mut hash, digest.len() as _, digest.as_mut_ptr());
+                }
+                digest
+            }
+        }
+
+        impl Signature for $ctx {
+            fn sign(&mut self, msg: &[u8]) -> Vec<u8> {
+                let digest = Self::digest(msg);
+                let mut sig = vec![0u8; 2 * self.len];
+                unsafe {
+                    let mut signature: dsa_signature = mem::zeroed();
+                    nettle_dsa_signature_init(&mut signature);
+                    nettle_ecdsa_sign(
+                        &self.key.private,
+                        ptr::null_mut(),
+                        Some(random),
+                        digest.len() as _,
+                        digest.as_ptr(),
+                        &mut signature,
+                    );
+                    let (r, s) = sig.split_at_mut(self.len);
+                    nettle_mpz_get_str_256(r.len() as _, r.as_mut_ptr(), signature.r.as_ptr());
+                    nettle_mpz_get_str_256(s.len() as _, s.as_mut_ptr(), signature.s.as_ptr());
+                    nettle_dsa_signature_clear(&mut signature);
+                }
+                sig
+            }
+
+            fn verify(&mut self, msg: &[u8], sig: &[u8]) -> bool {
+                let digest = Self::digest(msg);
+                if sig.len() != 2 * self.len {
+                    return false;
+                }
+                unsafe {
+                    let mut signature: dsa_signature = mem::zeroed();
+                    nettle_dsa_signature_init(&mut signature);
+                    let (r, s) = sig.split_at(self.len);
+                    nettle_mpz_set_str_256_u(signature.r.as_mut_ptr(), r.len() as _, r.as_ptr());
+                    nettle_mpz_set_str_256_u(signature.s.as_mut_ptr(), s.len() as _, s.as_ptr());
+                    let valid = nettle_ecdsa_verify(
+                        &self.key.public,
+                        digest.len() as _,
+                        digest.as_ptr(),
+                        &signature,
+                    ) != 0;
+                    nettle_dsa_signature_clear(&mut signature);
+                    valid
+                }
+            }
+        }
+    };
+}
+
+ecdsa!(
+    EcdsaP256Sha256CtxBuilder,
+    EcdsaP256Sha256Ctx,
+    nettle_get_secp_256r1,
+    sha256_ctx,
+    SHA256_DIGEST_SIZE,
+    nettle_sha256_init,
+    nettle_sha256_update,
+    nettle_sha256_digest
+);
+
+// SHA-384 shares its context and update function with SHA-512.
+ecdsa!(
+    EcdsaP384Sha384CtxBuilder,
+    EcdsaP384Sha384Ctx,
+    nettle_get_secp_384r1,
+    sha512_ctx,
+    SHA384_DIGEST_SIZE,
+    nettle_sha384_init,
+    nettle_sha512_update,
+    nettle_sha384_digest
+);
+
+ecdsa!(
+    EcdsaP521Sha512CtxBuilder,
+    EcdsaP521Sha512Ctx,
+    nettle_get_secp_521r1,
+    sha512_ctx,
+    SHA512_DIGEST_SIZE,
+    nettle_sha512_init,
+    nettle_sha512_update,
+    nettle_sha512_digest
+);
+
+macro_rules! eddsa {
+    ($builder:ident, $ctx:ident, $key_size:expr, $signature_size:expr, $public_key:ident, $sign:ident, $verify:ident) => {
+        pub struct $builder;
+
+        impl $builder {
+            pub fn new() -> Self {
+                Self
+            }
+        }
+
+        impl SignatureBuilder for $builder {
+            fn generate(&mut self) -> Box<dyn Signature> {
+                let mut private = [0u8; $key_size as usize];
+                rand::thread_rng().fill(&mut private);
+                let mut public = [0u8; $key_size as usize];
+                unsafe {
+                    $public_key(public.as_mut_ptr(), private.as_ptr());
+                }
+                Box::new($ctx { public, private })
+            }
+        }
+
+        pub struct $ctx {
+            public: [u8; $key_size as usize],
+            private: [u8; $key_size as usize],
+        }
+
+        impl Signature for $ctx {
+            fn sign(&mut self, msg: &[u8]) -> Vec<u8> {
+                let mut sig = vec![0u8; $signature_size as usize];
+                unsafe {
+                    $sign(
+                        self.public.as_ptr(),
+                        self.private.as_ptr(),
+                        msg.len() as _,
+                        msg.as_ptr(),
+                        sig.as_mut_ptr(),
+                    );
+                }
+                sig
+            }
+
+            fn verify(&mut self, msg: &[u8], sig: &[u8]) -> bool {
+                if sig.len() != $signature_size as usize {
+                    return false;
+                }
+                unsafe {
+                    $verify(
+                        self.public.as_ptr(),
+                        msg.len() as _,
+                        msg.as_ptr(),
+                        sig.as_ptr(),
+                    ) != 0
+                }
+            }
+        }
+    };
+}
+
+eddsa!(
+    Ed25519CtxBuilder,
+    Ed25519Ctx,
+    ED25519_KEY_SIZE,
+    ED25519_SIGNATURE_SIZE,
+    nettle_ed25519_sha512_public_key,
+    nettle_ed25519_sha512_sign,
+    nettle_ed25519_sha512_verify
+);
+
+eddsa!(
+    Ed448CtxBuilder,
+    Ed448Ctx,
+    ED448_KEY_SIZE,
+    ED448_SIGNATURE_SIZE,
+    nettle_ed448_shake256_public_key,
+    nettle_ed448_shake256_sign,
+    nettle_ed448_shake256_verify
+);
+
+macro_rules! montgomery {
+    ($builder:ident, $ctx:ident, $size:expr, $mul_g:ident, $mul:ident) => {
+        pub struct $builder;
+
+        impl $builder {
+            pub fn new() -> Self {
+                Self
+            }
+        }
+
+        impl KeyAgreementBuilder for $builder {
+            fn generate(&mut self) -> Box<dyn KeyAgreement> {
+                let mut private = [0u8; $size as usize];
+                rand::thread_rng().fill(&mut private);
+                let mut public = [0u8; $size as usize];
+                unsafe {
+                    $mul_g(public.as_mut_ptr(), private.as_ptr());
+                }
+                Box::new($ctx { public, private })
+            }
+        }
+
+        pub struct $ctx {
+            public: [u8; $size as usize],
+            private: [u8; $size as usize],
+        }
+
+        impl KeyAgreement for $ctx {
+            fn public_key(&self) -> Vec<u8> {
+                self.public.to_vec()
+            }
+
+            fn derive(&mut self, peer: &[u8]) -> Vec<u8> {
+                if peer.len() != $size as usize {
+                    return Vec::new();
+                }
+                let mut secret = vec![0u8; $size as usize];
+                unsafe {
+                    $mul(secret.as_mut_ptr(), self.private.as_ptr(), peer.as_ptr());
+                }
+                secret
+            }
+        }
+    };
+}
+
+montgomery!(
+    X25519CtxBuilder,
+    X25519Ctx,
+    CURVE25519_SIZE,
+    nettle_curve25519_mul_g,
+    nettle_curve25519_mul
+);
+
+montgomery!(
+    X448CtxBuilder,
+    X448Ctx,
+    CURVE448_SIZE,
+    nettle_curve448_mul_g,
+    nettle_curve448_mul
+);
+
+macro_rules! ecdh {
+    ($builder:ident, $curve:ident) => {
+        pub struct $builder;
+
+        impl $builder {
+            pub fn new() -> Self {
+                Self
+            }
+        }
+
+        impl KeyAgreementBuilder for $builder {
+            fn generate(&mut self) -> Box<dyn KeyAgreement> {
+                let curve = unsafe { $curve() };
+                Box::new(EcdhCtx {
+                    curve,
+                    key: EccKeyPair::generate(curve),
+                    len: unsafe { (nettle_ecc_bit_size(curve) as usize).div_ceil(8) },
+                })
+            }
+        }
+    };
+}
+
+ecdh!(EcdhP256CtxBuilder, nettle_get_secp_256r1);
+ecdh!(EcdhP384CtxBuilder, nettle_get_secp_384r1);
+
+/// Public keys are encoded as uncompressed points, and the shared
+/// secret is the x coordinate of the product.
+pub struct EcdhCtx {
+    curve: *const ecc_curve,
+    key: EccKeyPair,
+    len: usize,
+}
+
+impl KeyAgreement for EcdhCtx {
+    fn public_key(&self) -> Vec<u8> {
+        let mut x = Mpz::new();
+        let mut y = Mpz::new();
+        unsafe {
+            nettle_ecc_point_get(&self.key.public, x.0.as_mut_ptr(), y.0.as_mut_ptr());
+        }
+        let mut public = vec![4u8];
+        public.extend(x.to_bytes(self.len));
+        public.extend(y.to_bytes(self.len));
+        public
+    }
+
+    fn derive(&mut self, peer: &[u8]) -> Vec<u8> {
+        if peer.len() != 1 + 2 * self.len || peer[0] != 4 {
+            return Vec::new();
+        }
+        let mut x = Mpz::from_bytes(&peer[1..1 + self.len]);
+        let mut y = Mpz::from_bytes(&peer[1 + self.len..]);
+        unsafe {
+            let mut point: ecc_point = mem::zeroed();
+            let mut product: ecc_point = mem::zeroed();
+            nettle_ecc_point_init(&mut point, self.curve);
+            nettle_ecc_point_init(&mut product, self.curve);
+
+            let mut secret = Vec::new();
+            if nettle_ecc_point_set(&mut point, x.0.as_ptr(), y.0.as_ptr()) != 0 {
+                nettle_ecc_point_mul(&mut product, &self.key.private, &point);
+                nettle_ecc_point_get(&product, x.0.as_mut_ptr(), y.0.as_mut_ptr());
+                secret = x.to_bytes(self.len);
+            }
+
+            nettle_ecc_point_clear(&mut point);
+            nettle_ecc_point_clear(&mut product);
+            secret
+        }
+    }
+}
+
+// RFC 7919, appendix A.
+const FFDHE2048_P: &str = concat!(
+    "FFFFFFFFFFFFFFFFADF85458A2BB4A9AAFDC5620273D3CF1D8B9C583CE2D3695",
+    "A9E13641146433FBCC939DCE249B3EF97D2FE363630C75D8F681B202AEC4617A",
+    "D3DF1ED5D5FD65612433F51F5F066ED0856365553DED1AF3B557135E7F57C935",
+    "984F0C70E0E68B77E2A689DAF3EFE8721DF158A136ADE73530ACCA4F483A797A",
+    "BC0AB182B324FB61D108A94BB2C8E3FBB96ADAB760D7F4681D4F42A3DE394DF4",
+    "AE56EDE76372BB190B07A7C8EE0A6D709E02FCE1CDF7E2ECC03404CD28342F61",
+    "9172FE9CE98583FF8E4F1232EEF28183C3FE3B1B4C6FAD733BB5FCBC2EC22005",
+    "C58EF1837D1683B2C6F34A26C1B2EFFA886B423861285C97FFFFFFFFFFFFFFFF",
+    "\0"
+);
+
+const FFDHE3072_P: &str = concat!(
+    "FFFFFFFFFFFFFFFFADF85458A2BB4A9AAFDC5620273D3CF1D8B9C583CE2D3695",
+    "A9E13641146433FBCC939DCE249B3EF97D2FE363630C75D8F681B202AEC4617A",
+    "D3DF1ED5D5FD65612433F51F5F066ED0856365553DED1AF3B557135E7F57C935",
+    "984F0C70E0E68B77E2A689DAF3EFE8721DF158A136ADE73530ACCA4F483A797A",
+    "BC0AB182B324FB61D108A94BB2C8E3FBB96ADAB760D7F4681D4F42A3DE394DF4",
+    "AE56EDE76372BB190B07A7C8EE0A6D709E02FCE1CDF7E2ECC03404CD28342F61",
+    "9172FE9CE98583FF8E4F1232EEF28183C3FE3B1B4C6FAD733BB5FCBC2EC22005",
+    "C58EF1837D1683B2C6F34A26C1B2EFFA886B4238611FCFDCDE355B3B6519035B",
+    "BC34F4DEF99C023861B46FC9D6E6C9077AD91D2691F7F7EE598CB0FAC186D91C",
+    "AEFE130985139270B4130C93BC437944F4FD4452E2D74DD364F2E21E71F54BFF",
+    "5CAE82AB9C9DF69EE86D2BC522363A0DABC521979B0DEADA1DBF9A42D5C4484E",
+    "0ABCD06BFA53DDEF3C1B20EE3FD59D7C25E41D2B66C62E37FFFFFFFFFFFFFFFF",
+    "\0"
+);
+
+const FFDHE4096_P: &str = concat!(
+    "FFFFFFFFFFFFFFFFADF85458A2BB4A9AAFDC5620273D3CF1D8B9C583CE2D3695",
+    "A9E13641146433FBCC939DCE249B3EF97D2FE363630C75D8F681B202AEC4617A",
+    "D3DF1ED5D5FD65612433F51F5F066ED0856365553DED1AF3B557135E7F57C935",
+    "984F0C70E0E68B77E2A689DAF3EFE8721DF158A136ADE73530ACCA4F483A797A",
+    "BC0AB182B324FB61D108A94BB2C8E3FBB96ADAB760D7F4681D4F42A3DE394DF4",
+    "AE56EDE76372BB190B07A7C8EE0A6D709E02FCE1CDF7E2ECC03404CD28342F61",
+    "9172FE9CE98583FF8E4F1232EEF28183C3FE3B1B4C6FAD733BB5FCBC2EC22005",
+    "C58EF1837D1683B2C6F34A26C1B2EFFA886B4238611FCFDCDE355B3B6519035B",
+    "BC34F4DEF99C023861B46FC9D6E6C9077AD91D2691F7F7EE598CB0FAC186D91C",
+    "AEFE130985139270B4130C93BC437944F4FD4452E2D74DD364F2E21E71F54BFF",
+    "5CAE82AB9C9DF69EE86D2BC522363A0DABC521979B0DEADA1DBF9A42D5C4484E",
+    "0ABCD06BFA53DDEF3C1B20EE3FD59D7C25E41D2B669E1EF16E6F52C3164DF4FB",
+    "7930E9E4E58857B6AC7D5F42D69F6D187763CF1D5503400487F55BA57E31CC7A",
+    "7135C886EFB4318AED6A1E012D9E6832A907600A918130C46DC778F971AD0038",
+    "092999A333CB8B7A1A1DB93D7140003C2A4ECEA9F98D0ACC0A8291CDCEC97DCF",
+    "8EC9B55A7F88A46B4DB5A851F44182E1C68A007E5E655F6AFFFFFFFFFFFFFFFF",
+    "\0"
+);
+
+macro_rules! ffdhe {
+    ($builder:ident, $prime:ident, $private_bits:expr) => {
+        pub struct $builder;
+
+        impl $builder {
+            pub fn new() -> Self {
+                Self
+            }
+        }
+
+        impl KeyAgreementBuilder for $builder {
+            fn generate(&mut self) -> Box<dyn KeyAgreement> {
+                Box::new(FfdheCtx::generate($prime, $private_bits))
+            }
+        }
+    };
+}
+
+// Private exponent lengths follow the ones OpenSSL uses for these groups.
+ffdhe!(Ffdhe2048CtxBuilder, FFDHE2048_P, 225);
+ffdhe!(Ffdhe3072CtxBuilder, FFDHE3072_P, 275);
+ffdhe!(Ffdhe4096CtxBuilder, FFDHE4096_P, 325);
+
+/// Finite-field Diffie-Hellman with generator 2, on top of GMP since
+/// nettle has no DH interface.
+pub struct FfdheCtx {
+    p: Mpz,
+    private: Mpz,
+    public: Mpz,
+    len: usize,
+}
+
+impl FfdheCtx {
+    fn generate(prime: &str, private_bits: u32) -> Self {
+        let p = Mpz::from_hex(prime);
+        let len = unsafe { __gmpz_sizeinbase(p.0.as_ptr(), 2).div_ceil(8) };
+
+        let mut bytes = vec![0u8; (private_bits as usize).div_ceil(8)];
+        rand::thread_rng().fill(bytes.as_mut_slice());
+        let mut private = Mpz::from_bytes(&bytes);
+        let mut public = Mpz::new();
+        unsafe {
+            __gmpz_tdiv_r_2exp(
+                private.0.as_mut_ptr(),
+                private.0.as_ptr(),
+                private_bits as _,
+            );
+
+            let mut g = Mpz::new();
+            __gmpz_set_ui(g.0.as_mut_ptr(), 2);
+            __gmpz_powm_sec(
+                public.0.as_mut_ptr(),
+                g.0.as_ptr(),
+                private.0.as_ptr(),
+                p.0.as_ptr(),
+            );
+        }
+
+        Self {
+            p,
+            private,
+            public,
+            len: len as usize,
+        }
+    }
+}
+
+impl KeyAgreement for FfdheCtx {
+    fn public_key(&self) -> Vec<u8> {
+        self.public.to_bytes(self.len)
+    }
+
+    fn derive(&mut self, peer: &[u8]) -> Vec<u8> {
+        let y = Mpz::from_bytes(peer);
+        let mut z = Mpz::new();
+        unsafe {
+            // Reject y outside of [2, p - 2], as required by RFC 7919.
+            __gmpz_add_ui(z.0.as_mut_ptr(), y.0.as_ptr(), 1);
+            if __gmpz_cmp_ui(y.0.as_ptr(), 1) <= 0
+                || __gmpz_cmp(z.0.as_ptr(), self.p.0.as_ptr()) >= 0
+            {
+                return Vec::new();
+            }
+
+            __gmpz_powm_sec(
+                z.0.as_mut_ptr(),
+                y.0.as_ptr(),
+                self.private.0.as_ptr(),
+                self.p.0.as_ptr(),
+            );
+        }
+        z.to_bytes(self.len)
+    }
+}
+
+macro_rules! scalar_mul {
+    ($builder:ident, $curve:ident) => {
+        pub struct $builder;
+
+        impl $builder {
+            pub fn new() -> Self {
+                Self
+            }
+        }
+
+        impl ScalarMulBuilder for $builder {
+            fn generate(&mut self) -> Box<dyn ScalarMul> {
+                unsafe {
+                    let curve = $curve();
+                    let mut result: ecc_point = mem::zeroed();
+                    nettle_ecc_point_init(&mut result, curve);
+                    Box::new(EccScalarMulCtx {
+                        key: EccKeyPair::generate(curve),
+                        point: EccKeyPair::generate(curve),
+                        result,
+                    })
+                }
+            }
+        }
+    };
+}
+
+scalar_mul!(P256ScalarMulCtxBuilder, nettle_get_secp_256r1);
+scalar_mul!(P384ScalarMulCtxBuilder, nettle_get_secp_384r1);
+
+pub struct EccScalarMulCtx {
+    key: EccKeyPair,
+    point: EccKeyPair,
+    result: ecc_point,
+}
+
+impl ScalarMul for EccScalarMulCtx {
+    fn mul_g(&mut self) {
+        unsafe {
+            nettle_ecc_point_mul_g(&mut self.result, &self.key.private);
+        }
+    }
+
+    fn mul(&mut self) {
+        unsafe {
+            nettle_ecc_point_mul(&mut self.result, &self.key.private, &self.point.public);
+        }
+    }
+}
+
+impl Drop for EccScalarMulCtx {
+    fn drop(&mut self) {
+        unsafe {
+            nettle_ecc_point_clear(&mut self.result);
+        }
+    }
+}
+
+pub struct Curve25519ScalarMulCtxBuilder;
+
+impl Curve25519ScalarMulCtxBuilder {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl ScalarMulBuilder for Curve25519ScalarMulCtxBuilder {
+    fn generate(&mut self) -> Box<dyn ScalarMul> {
+        let mut rng = rand::thread_rng();
+        let mut scalar = [0u8; CURVE25519_SIZE as usize];
+        rng.fill(&mut scalar);
+        let mut other = [0u8; CURVE25519_SIZE as usize];
+        rng.fill(&mut other);
+        let mut point = [0u8; CURVE25519_SIZE as usize];
+        unsafe {
+            nettle_curve25519_mul_g(point.as_mut_ptr(), other.as_ptr());
+        }
+        Box::new(Curve25519ScalarMulCtx {
+            scalar,
+            point,
+            result: [0u8; CURVE25519_SIZE as usize],
+        })
+    }
+}
+
+/// Montgomery ladder on u-coordinates, as used by X25519.
+pub struct Curve25519ScalarMulCtx {
+    scalar: [u8; CURVE25519_SIZE as usize],
+    point: [u8; CURVE25519_SIZE as usize],
+    result: [u8; CURVE25519_SIZE as usize],
+}
+
+impl ScalarMul for Curve25519ScalarMulCtx {
+    fn mul_g(&mut self) {
+        unsafe {
+            nettle_curve25519_mul_g(self.result.as_mut_ptr(), self.scalar.as_ptr());
+        }
+    }
+
+    fn mul(&mut self) {
+        unsafe {
+            nettle_curve25519_mul(
+                self.result.as_mut_ptr(),
+                self.scalar.as_ptr(),
+                self.point.as_ptr(),
+            );
+        }
+    }
+}
+
+/// RSA-OAEP was added in nettle 3.10.
+#[cfg(have_rsa_oaep)]
+pub struct RsaOaepSha256CtxBuilder {
+    bits: u32,
+}
+
+#[cfg(have_rsa_oaep)]
+impl RsaOaepSha256CtxBuilder {
+    pub fn new(bits: u32) -> Self {
+        Self { bits }
+    }
+}
+
+#[cfg(have_rsa_oaep)]
+impl AsymmetricCipherBuilder for RsaOaepSha256CtxBuilder {
+    fn generate(&mut self) -> Box<dyn AsymmetricCipher> {
+        Box::new(RsaOaepSha256Ctx {
+            key: RsaKeyPair::generate(self.bits),
+        })
+    }
+}
+
+#[cfg(have_rsa_oaep)]
+pub struct RsaOaepSha256Ctx {
+    key: RsaKeyPair,
+}
+
+#[cfg(have_rsa_oaep)]
+impl AsymmetricCipher for RsaOaepSha256Ctx {
+    fn encrypt(&mut self, ptext: &[u8]) -> Vec<u8> {
+        let mut ctext = vec![0u8; self.key.public.size as usize];
+        unsafe {
+            nettle_rsa_oaep_sha256_encrypt(
+                &self.key.public,
+                ptr::null_mut(),
+                Some(random),
+                0,
+                ptr::null(),
+                ptext.len() as _,
+                ptext.as_ptr(),
+                ctext.as_mut_ptr(),
+            );
+        }
+        ctext
+    }
+
+    fn decrypt(&mut self, ctext: &[u8]) -> Vec<u8> {
+        let mut ptext = vec![0u8; self.key.public.size as usize];
+        let mut length = ptext.len() as size_t;
+        unsafe {
+            nettle_rsa_oaep_sha256_decrypt(
+                &self.key.public,
+                &self.key.private,
+                ptr::null_mut(),
+                Some(random),
+                0,
+                ptr::null(),
+                &mut length,
+                ptext.as_mut_ptr(),
+                ctext.as_ptr(),
+            );
+        }
+        ptext.truncate(length as usize);
+        ptext
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rsa_sign_verify() {
+        let mut rng = rand::thread_rng();
+
+        let mut data_bytes = vec![0u8; 1024];
+        rng.fill(data_bytes.as_mut_slice());
+
+        let mut builder = RsaPssSha256CtxBuilder::new(2048);
+
+        let mut ctx = builder.generate();
+        let sig = ctx.sign(&data_bytes);
+
+        assert!(ctx.verify(&data_bytes, &sig));
+
+        data_bytes[0] ^= 1;
+        assert!(!ctx.verify(&data_bytes, &sig));
+    }
+
+    #[test]
+    fn ecdsa_sign_verify() {
+        let mut rng = rand::thread_rng();
+
+        let mut data_bytes = vec![0u8; 1024];
+        rng.fill(data_bytes.as_mut_slice());
+
+        let mut builder = EcdsaP521Sha512CtxBuilder::new();
+
+        let mut ctx = builder.generate();
+        let sig = ctx.sign(&data_bytes);
+
+        assert!(ctx.verify(&data_bytes, &sig));
+
+        data_bytes[0] ^= 1;
+        assert!(!ctx.verify(&data_bytes, &sig));
+    }
+
+    // RFC 8032, section 7.1, test 1.
+    #[test]
+    fn ed25519_rfc8032() {
+        let private = [
+            0x9d, 0x61, 0xb1, 0x9d, 0xef, 0xfd, 0x5a, 0x60, 0xba, 0x84, 0x4a, 0xf4, 0x92, 0xec,
+            0x2c, 0xc4, 0x44, 0x49, 0xc5, 0x69, 0x7b, 0x32, 0x69, 0x19, 0x70, 0x3b, 0xac, 0x03,
+            0x1c, 0xae, 0x7f, 0x60,
+        ];
+        let public = [
+            0xd7, 0x5a, 0x98, 0x01, 0x82, 0xb1, 0x0a, 0xb7, 0xd5, 0x4b, 0xfe, 0xd3, 0xc9, 0x64,
+            0x07, 0x3a, 0x0e, 0xe1, 0x72, 0xf3, 0xda, 0xa6, 0x23, 0x25, 0xaf, 0x02, 0x1a, 0x68,
+            0xf7, 0x07, 0x51, 0x1a,
+        ];
+        let signature = [
+            0xe5, 0x56, 0x43, 0x00, 0xc3, 0x60, 0xac, 0x72, 0x90, 0x86, 0xe2, 0xcc, 0x80, 0x6e,
+            0x82, 0x8a, 0x84, 0x87, 0x7f, 0x1e, 0xb8, 0xe5, 0xd9, 0x74, 0xd8, 0x73, 0xe0, 0x65,
+            0x22, 0x49, 0x01, 0x55, 0x5f, 0xb8, 0x82, 0x15, 0x90, 0xa3, 0x3b, 0xac, 0xc6, 0x1e,
+            0x39, 0x70, 0x1c, 0xf9, 0xb4, 0x6b, 0xd2, 0x5b, 0xf5, 0xf0, 0x59, 0x5b, 0xbe, 0x24,
+            0x65, 0x51, 0x41, 0x43, 0x8e, 0x7a, 0x10, 0x0b,
+        ];
+
+        let mut ctx = Ed25519Ctx { public, private };
+        let sig = ctx.sign(&[]);
+
+        assert_eq!(sig, signature);
+        assert!(ctx.verify(&[], &sig));
+    }
+
+    // RFC 7748, section 6.1.
+    #[test]
+    fn x25519_rfc7748() {
+        let alice_private = [
+            0x77, 0x07, 0x6d, 0x0a, 0x73, 0x18, 0xa5, 0x7d, 0x3c, 0x16, 0xc1, 0x72, 0x51, 0xb2,
+            0x66, 0x45, 0xdf, 0x4c, 0x2f, 0x87, 0xeb, 0xc0, 0x99, 0x2a, 0xb1, 0x77, 0xfb, 0xa5,
+            0x1d, 0xb9, 0x2c, 0x2a,
+        ];
+        let bob_public = [
+            0xde, 0x9e, 0xdb, 0x7d, 0x7b, 0x7d, 0xc1, 0xb4, 0xd3, 0x5b, 0x61, 0xc2, 0xec, 0xe4,
+            0x35, 0x37, 0x3f, 0x83, 0x43, 0xc8, 0x5b, 0x78, 0x67, 0x4d, 0xad, 0xfc, 0x7e, 0x14,
+            0x6f, 0x88, 0x2b, 0x4f,
+        ];
+        let shared_secret = [
+            0x4a, 0x5d, 0x9d, 0x5b, 0xa4, 0xce, 0x2d, 0xe1, 0x72, 0x8e, 0x3b, 0xf4, 0x80, 0x35,
+            0x0f, 0x25, 0xe0, 0x7e, 0x21, 0xc9, 0x47, 0xd1, 0x9e, 0x33, 0x76, 0xf0, 0x9b, 0x3c,
+            0x1e, 0x16, 0x17, 0x42,
+        ];
+
+        let mut ctx = X25519Ctx {
+            public: [0u8; CURVE25519_SIZE as usize],
+            private: alice_private,
+        };
+
+        assert_eq!(ctx.derive(&bob_public), shared_secret);
+    }
+
+    #[test]
+    fn ecdh_agree() {
+        let mut builder = EcdhP256CtxBuilder::new();
+
+        let mut alice = builder.generate();
+        let mut bob = builder.generate();
+
+        assert_eq!(
+            alice.derive(&bob.public_key()),
+            bob.derive(&alice.public_key())
+        );
+    }
+
+    #[test]
+    fn ffdhe_agree() {
+        let mut builder = Ffdhe2048CtxBuilder::new();
+
+        let mut alice = builder.generate();
+        let mut bob = builder.generate();
+
+        let secret = alice.derive(&bob.public_key());
+
+        assert_eq!(secret.len(), 256);
+        assert_eq!(secret, bob.derive(&alice.public_key()));
+    }
+}
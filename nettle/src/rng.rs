@@ -0,0 +1,98 @@
+// SPDX-License-Identifier: Apache-2.0
+
+#![allow(non_upper_case_globals)]
+#![allow(non_camel_case_types)]
+#![allow(non_snake_case)]
+#![allow(dead_code)]
+
+include!(concat!(env!("OUT_DIR"), "/rng.rs"));
+
+use cipher_bench::{Rng, RngBuilder};
+use rand::prelude::*;
+use std::mem;
+use std::ptr;
+
+pub struct Yarrow256CtxBuilder;
+
+impl Yarrow256CtxBuilder {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl RngBuilder for Yarrow256CtxBuilder {
+    fn for_generation(&mut self) -> Box<dyn Rng> {
+        let mut seed = [0u8; 32];
+        rand::thread_rng().fill(&mut seed);
+        unsafe {
+            let mut ctx: yarrow256_ctx = mem::zeroed();
+            nettle_yarrow256_init(&mut ctx, 0, ptr::null_mut());
+            nettle_yarrow256_seed(&mut ctx, seed.len() as _, seed.as_ptr());
+            Box::new(Yarrow256Ctx { ctx })
+        }
+    }
+}
+
+/// Yarrow without entropy sources, seeded once.
+pub struct Yarrow256Ctx {
+    ctx: yarrow256_ctx,
+}
+
+impl Rng for Yarrow256Ctx {
+    fn fill(&mut self, dst: &mut [u8]) {
+        unsafe {
+            nettle_yarrow256_random(&mut self.ctx, dst.len() as _, dst.as_mut_ptr());
+        }
+    }
+}
+
+/// Knuth's lagged Fibonacci generator, which nettle provides for
+/// non-cryptographic uses such as tests.
+pub struct KnuthLfibCtxBuilder;
+
+impl KnuthLfibCtxBuilder {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl RngBuilder for KnuthLfibCtxBuilder {
+    fn for_generation(&mut self) -> Box<dyn Rng> {
+        unsafe {
+            let mut ctx: knuth_lfib_ctx = mem::zeroed();
+            nettle_knuth_lfib_init(&mut ctx, rand::thread_rng().gen());
+            Box::new(KnuthLfibCtx { ctx })
+        }
+    }
+}
+
+pub struct KnuthLfibCtx {
+    ctx: knuth_lfib_ctx,
+}
+
+impl Rng for KnuthLfibCtx {
+    fn fill(&mut self, dst: &mut [u8]) {
+        unsafe {
+            nettle_knuth_lfib_random(&mut self.ctx, dst.len() as _, dst.as_mut_ptr());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn yarrow256_distinct() {
+        let mut builder = Yarrow256CtxBuilder::new();
+
+        let mut ctx = builder.for_generation();
+
+        let mut first = [0u8; 32];
+        let mut second = [0u8; 32];
+        ctx.fill(&mut first);
+        ctx.fill(&mut second);
+
+        assert_ne!(first, second);
+    }
+}
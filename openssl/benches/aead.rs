@@ -2,25 +2,88 @@
 
 use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
 
-use cipher_bench::{bench_aead, AeadAlgorithm};
+use cipher_bench::{
+    bench_aead, bench_aead_messages, bench_aead_rekey, bench_aead_setup, AeadAlgorithm,
+    SelectedMeasurement, SizeSweep, REKEY_INTERVALS,
+};
 use openssl::Aes128GcmCtxBuilder;
 use std::convert::TryInto;
 
-pub fn aeads(c: &mut Criterion) {
+pub fn aeads(c: &mut Criterion<SelectedMeasurement>) {
     let mut group = c.benchmark_group("openssl/aeads");
-    let parameters: Vec<usize> = (1..=cipher_bench::ITER).collect();
+    let sweep = SizeSweep::for_benchmark("aeads", 16, SizeSweep::default());
 
-    for i in parameters {
-        group.throughput(Throughput::Bytes(
-            (i * cipher_bench::STEP).try_into().unwrap(),
-        ));
+    for len in sweep.sizes() {
+        group.throughput(Throughput::Bytes(len.try_into().unwrap()));
 
         let builder = Aes128GcmCtxBuilder::new();
-        bench_aead(&mut group, AeadAlgorithm::Aes128Gcm, builder, i);
+        bench_aead(&mut group, AeadAlgorithm::Aes128Gcm, builder, len);
     }
 
     group.finish();
 }
 
-criterion_group!(benches, aeads);
+// Record-sized messages, each sealed under its own nonce. Criterion
+// reports a single throughput per benchmark, so the sweep is run once
+// for messages per second and once for bytes per second.
+pub fn aead_messages(c: &mut Criterion<SelectedMeasurement>) {
+    let sweep = SizeSweep::for_benchmark(
+        "aead-messages",
+        1,
+        SizeSweep::List(vec![20, 64, 256, 576, 1200, 1500]),
+    );
+
+    for (name, per_byte) in [
+        ("openssl/aead-messages", false),
+        ("openssl/aead-message-bytes", true),
+    ] {
+        let mut group = c.benchmark_group(name);
+
+        for len in sweep.sizes() {
+            group.throughput(if per_byte {
+                Throughput::Bytes(len.try_into().unwrap())
+            } else {
+                Throughput::Elements(1)
+            });
+
+            // Without AAD, and with the 13 bytes of a TLS 1.2 record.
+            for aad_len in [0, 13] {
+                let builder = Aes128GcmCtxBuilder::new();
+                bench_aead_messages(&mut group, AeadAlgorithm::Aes128Gcm, builder, len, aad_len);
+            }
+        }
+
+        group.finish();
+    }
+}
+
+pub fn aead_setup(c: &mut Criterion<SelectedMeasurement>) {
+    let mut group = c.benchmark_group("openssl/aead-setup");
+    group.throughput(Throughput::Elements(1));
+
+    let builder = Aes128GcmCtxBuilder::new();
+    bench_aead_setup(&mut group, AeadAlgorithm::Aes128Gcm, builder);
+
+    group.finish();
+}
+
+// Session churn: 1 KiB messages with the key rotated every N messages.
+pub fn aead_rekey(c: &mut Criterion<SelectedMeasurement>) {
+    let mut group = c.benchmark_group("openssl/aead-rekey");
+    let len = 1024;
+    group.throughput(Throughput::Bytes(len.try_into().unwrap()));
+
+    for interval in REKEY_INTERVALS {
+        let builder = Aes128GcmCtxBuilder::new();
+        bench_aead_rekey(&mut group, AeadAlgorithm::Aes128Gcm, builder, len, interval);
+    }
+
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    config = cipher_bench::criterion();
+    targets = aeads, aead_messages, aead_setup, aead_rekey
+}
 criterion_main!(benches);
@@ -0,0 +1,29 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+
+use cipher_bench::{bench_scalar_mul, CurveAlgorithm, SelectedMeasurement};
+use openssl::{P256ScalarMulCtxBuilder, P384ScalarMulCtxBuilder};
+
+pub fn scalar_mul(c: &mut Criterion<SelectedMeasurement>) {
+    let mut group = c.benchmark_group("openssl/scalar-mul");
+    group.throughput(Throughput::Elements(1));
+
+    let builder = P256ScalarMulCtxBuilder::new();
+    bench_scalar_mul(&mut group, CurveAlgorithm::P256, builder);
+
+    let builder = P384ScalarMulCtxBuilder::new();
+    bench_scalar_mul(&mut group, CurveAlgorithm::P384, builder);
+
+    // OpenSSL does not expose Curve25519 through EC_POINT; its scalar
+    // multiplication is only reachable through X25519 key agreement.
+
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    config = cipher_bench::criterion();
+    targets = scalar_mul
+}
+criterion_main!(benches);
@@ -0,0 +1,58 @@
+// SPDX-License-Identifier: Apache-2.0
+
+#[cfg(have_hpke)]
+use criterion::Throughput;
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use cipher_bench::SelectedMeasurement;
+#[cfg(have_hpke)]
+use cipher_bench::{bench_hpke, HpkeAlgorithm, SizeSweep};
+#[cfg(have_hpke)]
+use openssl::{X25519Sha256Aes128GcmCtxBuilder, X25519Sha256ChaCha20Poly1305CtxBuilder};
+#[cfg(have_hpke)]
+use std::convert::TryInto;
+
+#[cfg(have_hpke)]
+pub fn hpke(c: &mut Criterion<SelectedMeasurement>) {
+    let mut group = c.benchmark_group("openssl/hpke");
+    // An empty plaintext isolates the cost of the sender and receiver
+    // setup.
+    let sweep = SizeSweep::for_benchmark(
+        "hpke",
+        1,
+        SizeSweep::List((0..=8).map(|i| i * 1024).collect()),
+    );
+
+    for len in sweep.sizes() {
+        group.throughput(Throughput::Bytes(len.try_into().unwrap()));
+
+        let builder = X25519Sha256Aes128GcmCtxBuilder::new();
+        bench_hpke(
+            &mut group,
+            HpkeAlgorithm::X25519Sha256Aes128Gcm,
+            builder,
+            len,
+        );
+
+        let builder = X25519Sha256ChaCha20Poly1305CtxBuilder::new();
+        bench_hpke(
+            &mut group,
+            HpkeAlgorithm::X25519Sha256ChaCha20Poly1305,
+            builder,
+            len,
+        );
+    }
+
+    group.finish();
+}
+
+// HPKE was added in OpenSSL 3.2.
+#[cfg(not(have_hpke))]
+pub fn hpke(_c: &mut Criterion<SelectedMeasurement>) {}
+
+criterion_group! {
+    name = benches;
+    config = cipher_bench::criterion();
+    targets = hpke
+}
+criterion_main!(benches);
@@ -0,0 +1,40 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use cipher_bench::{
+    bench_kbkdf, bench_kdf, bench_tls13_key_schedule, KbkdfAlgorithm, KdfAlgorithm,
+    SelectedMeasurement,
+};
+use openssl::{HkdfSha256CtxBuilder, HkdfSha384CtxBuilder, KbkdfCounterHmacSha256CtxBuilder};
+
+pub fn kdfs(c: &mut Criterion<SelectedMeasurement>) {
+    let mut group = c.benchmark_group("openssl/kdfs");
+    let parameters: Vec<usize> = vec![16, 32, 64];
+
+    for len in parameters {
+        let builder = HkdfSha256CtxBuilder::new();
+        bench_kdf(&mut group, KdfAlgorithm::HkdfSha256, builder, len);
+
+        let builder = HkdfSha384CtxBuilder::new();
+        bench_kdf(&mut group, KdfAlgorithm::HkdfSha384, builder, len);
+
+        let builder = KbkdfCounterHmacSha256CtxBuilder::new();
+        bench_kbkdf(&mut group, KbkdfAlgorithm::CounterHmacSha256, builder, len);
+    }
+
+    let builder = HkdfSha256CtxBuilder::new();
+    bench_tls13_key_schedule(&mut group, KdfAlgorithm::HkdfSha256, builder, 16);
+
+    let builder = HkdfSha384CtxBuilder::new();
+    bench_tls13_key_schedule(&mut group, KdfAlgorithm::HkdfSha384, builder, 32);
+
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    config = cipher_bench::criterion();
+    targets = kdfs
+}
+criterion_main!(benches);
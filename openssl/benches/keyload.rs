@@ -0,0 +1,60 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+
+use cipher_bench::{bench_key_decoder, KeyAlgorithm, KeyEncoding, KeyFormat, SelectedMeasurement};
+use openssl::{LegacyDecoderCtxBuilder, OsslDecoderCtxBuilder};
+
+const ALGORITHMS: [KeyAlgorithm; 3] = [
+    KeyAlgorithm::Rsa2048,
+    KeyAlgorithm::EcdsaP256,
+    KeyAlgorithm::Ed25519,
+];
+
+const FORMATS: [KeyFormat; 4] = [
+    KeyFormat::TypeSpecific,
+    KeyFormat::Pkcs8,
+    KeyFormat::EncryptedPkcs8,
+    KeyFormat::SubjectPublicKeyInfo,
+];
+
+const ENCODINGS: [KeyEncoding; 2] = [KeyEncoding::Der, KeyEncoding::Pem];
+
+pub fn decoder(c: &mut Criterion<SelectedMeasurement>) {
+    let mut group = c.benchmark_group("openssl/key-decoder");
+    group.throughput(Throughput::Elements(1));
+
+    for algorithm in ALGORITHMS {
+        for format in FORMATS {
+            for encoding in ENCODINGS {
+                let builder = OsslDecoderCtxBuilder::new();
+                bench_key_decoder(&mut group, algorithm, format, encoding, builder);
+            }
+        }
+    }
+
+    group.finish();
+}
+
+pub fn legacy(c: &mut Criterion<SelectedMeasurement>) {
+    let mut group = c.benchmark_group("openssl/key-decoder-legacy");
+    group.throughput(Throughput::Elements(1));
+
+    for algorithm in ALGORITHMS {
+        for format in FORMATS {
+            for encoding in ENCODINGS {
+                let builder = LegacyDecoderCtxBuilder::new();
+                bench_key_decoder(&mut group, algorithm, format, encoding, builder);
+            }
+        }
+    }
+
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    config = cipher_bench::criterion();
+    targets = decoder, legacy
+}
+criterion_main!(benches);
@@ -0,0 +1,39 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+
+use cipher_bench::{bench_mac, MacAlgorithm, SelectedMeasurement, SizeSweep};
+use openssl::{
+    Aes128CmacCtxBuilder, Aes128GmacCtxBuilder, HmacSha256CtxBuilder, Poly1305CtxBuilder,
+};
+use std::convert::TryInto;
+
+pub fn macs(c: &mut Criterion<SelectedMeasurement>) {
+    let mut group = c.benchmark_group("openssl/macs");
+    let sweep = SizeSweep::for_benchmark("macs", 1, SizeSweep::default());
+
+    for len in sweep.sizes() {
+        group.throughput(Throughput::Bytes(len.try_into().unwrap()));
+
+        let builder = Aes128CmacCtxBuilder::new();
+        bench_mac(&mut group, MacAlgorithm::Aes128Cmac, builder, len);
+
+        let builder = Aes128GmacCtxBuilder::new();
+        bench_mac(&mut group, MacAlgorithm::Aes128Gmac, builder, len);
+
+        let builder = HmacSha256CtxBuilder::new();
+        bench_mac(&mut group, MacAlgorithm::HmacSha256, builder, len);
+
+        let builder = Poly1305CtxBuilder::new();
+        bench_mac(&mut group, MacAlgorithm::Poly1305, builder, len);
+    }
+
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    config = cipher_bench::criterion();
+    targets = macs
+}
+criterion_main!(benches);
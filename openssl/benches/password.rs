@@ -0,0 +1,76 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use cipher_bench::{
+    bench_password_hash, PasswordHashAlgorithm, PasswordHashCost, SelectedMeasurement,
+};
+use openssl::{
+    Argon2idCtxBuilder, Pbkdf2Sha256CtxBuilder, Pbkdf2Sha512CtxBuilder, ScryptCtxBuilder,
+};
+
+pub fn password_hashes(c: &mut Criterion<SelectedMeasurement>) {
+    let mut group = c.benchmark_group("openssl/password-hashes");
+    group.sample_size(10);
+
+    for iterations in [1_000, 10_000, 100_000] {
+        let cost = PasswordHashCost {
+            time: iterations,
+            memory: 0,
+            parallelism: 0,
+        };
+
+        let builder = Pbkdf2Sha256CtxBuilder::new();
+        bench_password_hash(
+            &mut group,
+            PasswordHashAlgorithm::Pbkdf2Sha256,
+            builder,
+            cost,
+        );
+
+        let builder = Pbkdf2Sha512CtxBuilder::new();
+        bench_password_hash(
+            &mut group,
+            PasswordHashAlgorithm::Pbkdf2Sha512,
+            builder,
+            cost,
+        );
+    }
+
+    for log_n in [14, 15, 16, 17] {
+        let cost = PasswordHashCost {
+            time: 0,
+            memory: 1 << log_n,
+            parallelism: 1,
+        };
+
+        let builder = ScryptCtxBuilder::new();
+        bench_password_hash(&mut group, PasswordHashAlgorithm::Scrypt, builder, cost);
+    }
+
+    if Argon2idCtxBuilder::is_supported() {
+        // Two of the OWASP recommended configurations, and the RFC 9106
+        // second recommended option.
+        let parameters = [(2, 19 * 1024, 1), (1, 46 * 1024, 1), (3, 64 * 1024, 4)];
+
+        for (time, memory, parallelism) in parameters {
+            let cost = PasswordHashCost {
+                time,
+                memory,
+                parallelism,
+            };
+
+            let builder = Argon2idCtxBuilder::new();
+            bench_password_hash(&mut group, PasswordHashAlgorithm::Argon2id, builder, cost);
+        }
+    }
+
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    config = cipher_bench::criterion();
+    targets = password_hashes
+}
+criterion_main!(benches);
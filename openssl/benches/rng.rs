@@ -0,0 +1,44 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+
+use cipher_bench::{
+    bench_rng, GetrandomCtxBuilder, RngAlgorithm, SelectedMeasurement, SizeSweep, RNG_LENGTHS,
+};
+use openssl::{
+    CtrDrbgAes256CtxBuilder, HashDrbgSha256CtxBuilder, RandBytesCtxBuilder, RandPrivBytesCtxBuilder,
+};
+use std::convert::TryInto;
+
+pub fn rng(c: &mut Criterion<SelectedMeasurement>) {
+    let mut group = c.benchmark_group("openssl/rng");
+    let sweep = SizeSweep::for_benchmark("rng", 1, SizeSweep::List(RNG_LENGTHS.to_vec()));
+
+    for len in sweep.sizes() {
+        group.throughput(Throughput::Bytes(len.try_into().unwrap()));
+
+        let builder = RandBytesCtxBuilder::new();
+        bench_rng(&mut group, RngAlgorithm::RandBytes, builder, len);
+
+        let builder = RandPrivBytesCtxBuilder::new();
+        bench_rng(&mut group, RngAlgorithm::RandPrivBytes, builder, len);
+
+        let builder = CtrDrbgAes256CtxBuilder::new();
+        bench_rng(&mut group, RngAlgorithm::CtrDrbgAes256, builder, len);
+
+        let builder = HashDrbgSha256CtxBuilder::new();
+        bench_rng(&mut group, RngAlgorithm::HashDrbgSha256, builder, len);
+
+        let builder = GetrandomCtxBuilder::new();
+        bench_rng(&mut group, RngAlgorithm::Getrandom, builder, len);
+    }
+
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    config = cipher_bench::criterion();
+    targets = rng
+}
+criterion_main!(benches);
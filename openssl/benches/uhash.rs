@@ -0,0 +1,38 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+
+use cipher_bench::{bench_universal_hash, SelectedMeasurement, SizeSweep, UniversalHashAlgorithm};
+use openssl::{Aes128GcmSivPolyvalCtxBuilder, Aes128GmacCtxBuilder, Poly1305CtxBuilder};
+use std::convert::TryInto;
+
+pub fn universal_hashes(c: &mut Criterion<SelectedMeasurement>) {
+    let mut group = c.benchmark_group("openssl/universal-hashes");
+    let sweep = SizeSweep::for_benchmark("universal-hashes", 16, SizeSweep::default());
+
+    for len in sweep.sizes() {
+        group.throughput(Throughput::Bytes(len.try_into().unwrap()));
+
+        let builder = Aes128GmacCtxBuilder::new();
+        bench_universal_hash(&mut group, UniversalHashAlgorithm::Ghash, builder, len);
+
+        // Includes the per-nonce key derivation, see
+        // UniversalHashAlgorithm::Polyval.
+        if Aes128GcmSivPolyvalCtxBuilder::is_supported() {
+            let builder = Aes128GcmSivPolyvalCtxBuilder::new();
+            bench_universal_hash(&mut group, UniversalHashAlgorithm::Polyval, builder, len);
+        }
+
+        let builder = Poly1305CtxBuilder::new();
+        bench_universal_hash(&mut group, UniversalHashAlgorithm::Poly1305, builder, len);
+    }
+
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    config = cipher_bench::criterion();
+    targets = universal_hashes
+}
+criterion_main!(benches);
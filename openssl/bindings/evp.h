@@ -1,5 +1,18 @@
 // SPDX-License-Identifier: Apache-2.0
 
+#include <openssl/bn.h>
 #include <openssl/conf.h>
+#include <openssl/decoder.h>
+#include <openssl/ec.h>
 #include <openssl/evp.h>
 #include <openssl/err.h>
+#include <openssl/kdf.h>
+#include <openssl/pem.h>
+#include <openssl/rand.h>
+#include <openssl/rsa.h>
+#include <openssl/x509.h>
+
+/* HPKE was added in OpenSSL 3.2. */
+#if __has_include(<openssl/hpke.h>)
+#include <openssl/hpke.h>
+#endif
@@ -3,10 +3,24 @@ extern crate bindgen;
 use std::env;
 use std::path::PathBuf;
 
+// Functions that are not available in all supported OpenSSL versions,
+// and the cfg flag to set when they are.
+const OPTIONAL_FUNCTIONS: &[(&str, &str)] = &[("OSSL_HPKE_seal", "have_hpke")];
+
+fn declares(code: &str, function: &str) -> bool {
+    code.match_indices(function).any(|(index, _)| {
+        !code[index + function.len()..].starts_with(|c: char| c == '_' || c.is_alphanumeric())
+    })
+}
+
 fn main() {
     println!("cargo:rustc-link-lib=crypto");
     println!("cargo:rerun-if-changed=bindings/evp.h");
 
+    for (_, cfg) in OPTIONAL_FUNCTIONS {
+        println!("cargo:rustc-check-cfg=cfg({})", cfg);
+    }
+
     let bindings = bindgen::Builder::default()
         .header("bindings/evp.h")
         .parse_callbacks(Box::new(bindgen::CargoCallbacks))
@@ -14,6 +28,13 @@ fn main() {
         .generate()
         .expect("Unable to generate bindings");
 
+    let code = bindings.to_string();
+    for (function, cfg) in OPTIONAL_FUNCTIONS {
+        if declares(&code, function) {
+            println!("cargo:rustc-cfg={}", cfg);
+        }
+    }
+
     let out_path = PathBuf::from(env::var("OUT_DIR").unwrap());
     bindings
         .write_to_file(out_path.join("evp.rs"))
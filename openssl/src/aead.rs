@@ -29,7 +29,10 @@ impl Aes128GcmCtxBuilder {
             );
             ctx
         };
-        Box::new(Aes128GcmCtx { ctx })
+        Box::new(Aes128GcmCtx {
+            ctx,
+            for_encryption,
+        })
     }
 }
 
@@ -50,6 +53,9 @@ impl AeadBuilder for Aes128GcmCtxBuilder {
 
 pub struct Aes128GcmCtx {
     ctx: *mut evp::EVP_CIPHER_CTX,
+    // EVP_EncryptInit_ex would silently turn a decryption context into
+    // an encryption one.
+    for_encryption: bool,
 }
 
 impl Aead for Aes128GcmCtx {
@@ -78,12 +84,72 @@ impl Aead for Aes128GcmCtx {
             );
         }
     }
+
+    fn set_nonce(&mut self, nonce: &[u8]) {
+        unsafe {
+            // A negative direction keeps the current one.
+            evp::EVP_CipherInit_ex(
+                self.ctx,
+                ptr::null(),
+                ptr::null_mut::<evp::ENGINE>(),
+                ptr::null(),
+                nonce.as_ptr() as _,
+                -1,
+            );
+        }
+    }
+
+    fn seal(&mut self, nonce: &[u8], aad: &[u8], ptext: &[u8], ctext: &mut [u8], tag: &mut [u8]) {
+        debug_assert!(self.for_encryption, "seal on a decryption context");
+        let mut outl = 0;
+        unsafe {
+            // Only the IV is set, the key schedule is kept.
+            evp::EVP_EncryptInit_ex(
+                self.ctx,
+                ptr::null(),
+                ptr::null_mut::<evp::ENGINE>(),
+                ptr::null(),
+                nonce.as_ptr() as _,
+            );
+            if !aad.is_empty() {
+                evp::EVP_EncryptUpdate(
+                    self.ctx,
+                    ptr::null_mut(),
+                    &mut outl,
+                    aad.as_ptr() as _,
+                    aad.len() as _,
+                );
+            }
+            evp::EVP_EncryptUpdate(
+                self.ctx,
+                ctext.as_mut_ptr() as *mut _,
+                &mut outl,
+                ptext.as_ptr() as _,
+                ptext.len() as _,
+            );
+            evp::EVP_EncryptFinal_ex(self.ctx, ctext.as_mut_ptr().add(outl as usize), &mut outl);
+            evp::EVP_CIPHER_CTX_ctrl(
+                self.ctx,
+                evp::EVP_CTRL_AEAD_GET_TAG as _,
+                tag.len() as _,
+                tag.as_mut_ptr() as _,
+            );
+        }
+    }
+}
+
+impl Drop for Aes128GcmCtx {
+    fn drop(&mut self) {
+        unsafe {
+            evp::EVP_CIPHER_CTX_free(self.ctx);
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use cipher_bench::AeadAlgorithm;
+    use cipher_bench::{check_seal, AeadAlgorithm};
     use rand::prelude::*;
     use std::convert::TryInto;
 
@@ -114,4 +180,9 @@ mod tests {
 
         assert_eq!(ptext, data_bytes);
     }
+
+    #[test]
+    fn seal_gcm_spec() {
+        check_seal(Aes128GcmCtxBuilder::new());
+    }
 }
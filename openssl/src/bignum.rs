@@ -0,0 +1,119 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::evp;
+use cipher_bench::{Bignum, BignumBuilder};
+use std::ptr;
+
+pub struct BnCtxBuilder;
+
+impl BnCtxBuilder {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+fn bn_from_bytes(bytes: &[u8]) -> *mut evp::BIGNUM {
+    unsafe { evp::BN_bin2bn(bytes.as_ptr(), bytes.len() as _, ptr::null_mut()) }
+}
+
+impl BignumBuilder for BnCtxBuilder {
+    fn for_operands(
+        &mut self,
+        base: &[u8],
+        multiplier: &[u8],
+        exponent: &[u8],
+        modulus: &[u8],
+    ) -> Box<dyn Bignum> {
+        Box::new(BnCtx::new(base, multiplier, exponent, modulus))
+    }
+}
+
+/// The Montgomery context is computed once, as RSA and DH keys cache it.
+pub struct BnCtx {
+    base: *mut evp::BIGNUM,
+    multiplier: *mut evp::BIGNUM,
+    exponent: *mut evp::BIGNUM,
+    modulus: *mut evp::BIGNUM,
+    result: *mut evp::BIGNUM,
+    ctx: *mut evp::BN_CTX,
+    mont: *mut evp::BN_MONT_CTX,
+}
+
+impl BnCtx {
+    fn new(base: &[u8], multiplier: &[u8], exponent: &[u8], modulus: &[u8]) -> Self {
+        unsafe {
+            let modulus = bn_from_bytes(modulus);
+            let ctx = evp::BN_CTX_new();
+            let mont = evp::BN_MONT_CTX_new();
+            let _ = evp::BN_MONT_CTX_set(mont, modulus, ctx);
+
+            Self {
+                base: bn_from_bytes(base),
+                multiplier: bn_from_bytes(multiplier),
+                exponent: bn_from_bytes(exponent),
+                modulus,
+                result: evp::BN_new(),
+                ctx,
+                mont,
+            }
+        }
+    }
+}
+
+impl Bignum for BnCtx {
+    fn mod_exp(&mut self) {
+        unsafe {
+            evp::BN_mod_exp_mont_consttime(
+                self.result,
+                self.base,
+                self.exponent,
+                self.modulus,
+                self.ctx,
+                self.mont,
+            );
+        }
+    }
+
+    fn mod_mul(&mut self) {
+        unsafe {
+            evp::BN_mod_mul(
+                self.result,
+                self.base,
+                self.multiplier,
+                self.modulus,
+                self.ctx,
+            );
+        }
+    }
+}
+
+impl Drop for BnCtx {
+    fn drop(&mut self) {
+        unsafe {
+            evp::BN_free(self.base);
+            evp::BN_free(self.multiplier);
+            evp::BN_free(self.exponent);
+            evp::BN_free(self.modulus);
+            evp::BN_free(self.result);
+            evp::BN_CTX_free(self.ctx);
+            evp::BN_MONT_CTX_free(self.mont);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mod_exp_mod_mul() {
+        // 4^13 mod 497 = 445, and 4 * 6 mod 497 = 24.
+        let mut ctx = BnCtx::new(&[4], &[6], &[13], &[0x01, 0xf1]);
+
+        ctx.mod_exp();
+        assert_eq!(unsafe { evp::BN_is_word(ctx.result, 445) }, 1);
+
+        ctx.mod_mul();
+        assert_eq!(unsafe { evp::BN_is_word(ctx.result, 24) }, 1);
+    }
+}
@@ -78,6 +78,28 @@ impl BlockCipher for Aes128CbcCtx {
             );
         }
     }
+
+    fn set_nonce(&mut self, nonce: &[u8]) {
+        unsafe {
+            // A negative direction keeps the current one.
+            evp::EVP_CipherInit_ex(
+                self.ctx,
+                ptr::null(),
+                ptr::null_mut::<evp::ENGINE>(),
+                ptr::null(),
+                nonce.as_ptr() as _,
+                -1,
+            );
+        }
+    }
+}
+
+impl Drop for Aes128CbcCtx {
+    fn drop(&mut self) {
+        unsafe {
+            evp::EVP_CIPHER_CTX_free(self.ctx);
+        }
+    }
 }
 
 #[cfg(test)]
@@ -114,4 +136,28 @@ mod tests {
 
         assert_eq!(ptext, data_bytes);
     }
+
+    #[test]
+    fn set_nonce_restarts_chain() {
+        let mut rng = rand::thread_rng();
+
+        let mut key_bytes = vec![0u8; BlockCipherAlgorithm::Aes128Cbc.key_len()];
+        rng.fill(key_bytes.as_mut_slice());
+
+        let mut nonce_bytes = vec![0u8; BlockCipherAlgorithm::Aes128Cbc.nonce_len()];
+        rng.fill(nonce_bytes.as_mut_slice());
+
+        let ptext = vec![0u8; 64];
+        let mut first = vec![0u8; 64];
+        let mut second = vec![0u8; 64];
+
+        let mut builder = Aes128CbcCtxBuilder::new();
+        let mut ctx = builder.nonce(&nonce_bytes).for_encryption(&key_bytes);
+
+        ctx.encrypt(&ptext, &mut first);
+        ctx.set_nonce(&nonce_bytes);
+        ctx.encrypt(&ptext, &mut second);
+
+        assert_eq!(first, second);
+    }
 }
@@ -0,0 +1,198 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::evp;
+use cipher_bench::{Hpke, HpkeBuilder};
+use std::ptr;
+
+fn suite(aead_id: u32) -> evp::OSSL_HPKE_SUITE {
+    evp::OSSL_HPKE_SUITE {
+        kem_id: evp::OSSL_HPKE_KEM_ID_X25519 as _,
+        kdf_id: evp::OSSL_HPKE_KDF_ID_HKDF_SHA256 as _,
+        aead_id: aead_id as _,
+    }
+}
+
+pub struct X25519Sha256Aes128GcmCtxBuilder;
+
+impl X25519Sha256Aes128GcmCtxBuilder {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl HpkeBuilder for X25519Sha256Aes128GcmCtxBuilder {
+    fn generate(&mut self) -> Box<dyn Hpke> {
+        Box::new(OsslHpkeCtx::generate(suite(
+            evp::OSSL_HPKE_AEAD_ID_AES_GCM_128,
+        )))
+    }
+}
+
+pub struct X25519Sha256ChaCha20Poly1305CtxBuilder;
+
+impl X25519Sha256ChaCha20Poly1305CtxBuilder {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl HpkeBuilder for X25519Sha256ChaCha20Poly1305CtxBuilder {
+    fn generate(&mut self) -> Box<dyn Hpke> {
+        Box::new(OsslHpkeCtx::generate(suite(
+            evp::OSSL_HPKE_AEAD_ID_CHACHA_POLY1305,
+        )))
+    }
+}
+
+/// A recipient key pair. Each seal and open creates a fresh
+/// OSSL_HPKE_CTX, since a sender context is bound to one encapsulation.
+pub struct OsslHpkeCtx {
+    suite: evp::OSSL_HPKE_SUITE,
+    public: Vec<u8>,
+    private: *mut evp::EVP_PKEY,
+}
+
+impl OsslHpkeCtx {
+    fn generate(suite: evp::OSSL_HPKE_SUITE) -> Self {
+        unsafe {
+            let mut public = vec![0u8; evp::OSSL_HPKE_get_public_encap_size(suite) as usize];
+            let mut publen = public.len() as _;
+            let mut private = ptr::null_mut::<evp::EVP_PKEY>();
+            let _ = evp::OSSL_HPKE_keygen(
+                suite,
+                public.as_mut_ptr(),
+                &mut publen,
+                &mut private,
+                ptr::null(),
+                0,
+                ptr::null_mut::<evp::OSSL_LIB_CTX>(),
+                ptr::null(),
+            );
+            public.truncate(publen as usize);
+            Self {
+                suite,
+                public,
+                private,
+            }
+        }
+    }
+}
+
+impl Hpke for OsslHpkeCtx {
+    fn public_key(&self) -> Vec<u8> {
+        self.public.clone()
+    }
+
+    fn seal(&mut self, peer: &[u8], info: &[u8], aad: &[u8], ptext: &[u8]) -> (Vec<u8>, Vec<u8>) {
+        unsafe {
+            let ctx = evp::OSSL_HPKE_CTX_new(
+                evp::OSSL_HPKE_MODE_BASE as _,
+                self.suite,
+                evp::OSSL_HPKE_ROLE_SENDER as _,
+                ptr::null_mut::<evp::OSSL_LIB_CTX>(),
+                ptr::null(),
+            );
+
+            let mut enc = vec![0u8; evp::OSSL_HPKE_get_public_encap_size(self.suite) as usize];
+            let mut enclen = enc.len() as _;
+            let _ = evp::OSSL_HPKE_encap(
+                ctx,
+                enc.as_mut_ptr(),
+                &mut enclen,
+                peer.as_ptr(),
+                peer.len() as _,
+                info.as_ptr(),
+                info.len() as _,
+            );
+            enc.truncate(enclen as usize);
+
+            let mut ctext = vec![
+                0u8;
+                evp::OSSL_HPKE_get_ciphertext_size(self.suite, ptext.len() as _)
+                    as usize
+            ];
+            let mut ctextlen = ctext.len() as _;
+            let _ = evp::OSSL_HPKE_seal(
+                ctx,
+                ctext.as_mut_ptr(),
+                &mut ctextlen,
+                aad.as_ptr(),
+                aad.len() as _,
+                ptext.as_ptr(),
+                ptext.len() as _,
+            );
+            ctext.truncate(ctextlen as usize);
+
+            evp::OSSL_HPKE_CTX_free(ctx);
+            (enc, ctext)
+        }
+    }
+
+    fn open(&mut self, enc: &[u8], info: &[u8], aad: &[u8], ctext: &[u8]) -> Vec<u8> {
+        unsafe {
+            let ctx = evp::OSSL_HPKE_CTX_new(
+                evp::OSSL_HPKE_MODE_BASE as _,
+                self.suite,
+                evp::OSSL_HPKE_ROLE_RECEIVER as _,
+                ptr::null_mut::<evp::OSSL_LIB_CTX>(),
+                ptr::null(),
+            );
+
+            let _ = evp::OSSL_HPKE_decap(
+                ctx,
+                enc.as_ptr(),
+                enc.len() as _,
+                self.private,
+                info.as_ptr(),
+                info.len() as _,
+            );
+
+            let mut ptext = vec![0u8; ctext.len()];
+            let mut ptextlen = ptext.len() as _;
+            let _ = evp::OSSL_HPKE_open(
+                ctx,
+                ptext.as_mut_ptr(),
+                &mut ptextlen,
+                aad.as_ptr(),
+                aad.len() as _,
+                ctext.as_ptr(),
+                ctext.len() as _,
+            );
+            ptext.truncate(ptextlen as usize);
+
+            evp::OSSL_HPKE_CTX_free(ctx);
+            ptext
+        }
+    }
+}
+
+impl Drop for OsslHpkeCtx {
+    fn drop(&mut self) {
+        unsafe {
+            evp::EVP_PKEY_free(self.private);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::prelude::*;
+
+    #[test]
+    fn hpke_roundtrip() {
+        let mut rng = rand::thread_rng();
+
+        let mut data_bytes = vec![0u8; 1024];
+        rng.fill(data_bytes.as_mut_slice());
+
+        let mut builder = X25519Sha256ChaCha20Poly1305CtxBuilder::new();
+
+        let mut ctx = builder.generate();
+        let peer = ctx.public_key();
+        let (enc, ctext) = ctx.seal(&peer, b"info", b"aad", &data_bytes);
+
+        assert_eq!(ctext.len(), data_bytes.len() + 16);
+        assert_eq!(ctx.open(&enc, b"info", b"aad", &ctext), data_bytes);
+    }
+}
@@ -0,0 +1,220 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::evp;
+use cipher_bench::{Kbkdf, KbkdfBuilder, Kdf, KdfBuilder};
+use std::os::raw::c_int;
+use std::ptr;
+
+/// Fetches the KDF and sets the parameters that stay the same for every
+/// derivation, so that they are not parsed again on each call.
+fn new_kdf_ctx(name: &[u8], params: &[evp::OSSL_PARAM]) -> *mut evp::EVP_KDF_CTX {
+    unsafe {
+        let kdf = evp::EVP_KDF_fetch(
+            ptr::null_mut::<evp::OSSL_LIB_CTX>(),
+            name.as_ptr() as _,
+            ptr::null(),
+        );
+        let ctx = evp::EVP_KDF_CTX_new(kdf);
+        evp::EVP_KDF_free(kdf);
+        evp::EVP_KDF_CTX_set_params(ctx, params.as_ptr());
+        ctx
+    }
+}
+
+fn new_hkdf_ctx(digest: &[u8]) -> *mut evp::EVP_KDF_CTX {
+    unsafe {
+        let params = [
+            evp::OSSL_PARAM_construct_utf8_string(
+                b"digest\0".as_ptr() as _,
+                digest.as_ptr() as *mut _,
+                0,
+            ),
+            evp::OSSL_PARAM_construct_end(),
+        ];
+        new_kdf_ctx(b"HKDF\0", &params)
+    }
+}
+
+pub struct HkdfSha256CtxBuilder;
+
+impl HkdfSha256CtxBuilder {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl KdfBuilder for HkdfSha256CtxBuilder {
+    fn for_derivation(&mut self) -> Box<dyn Kdf> {
+        Box::new(HkdfCtx {
+            ctx: new_hkdf_ctx(b"SHA256\0"),
+        })
+    }
+}
+
+pub struct HkdfSha384CtxBuilder;
+
+impl HkdfSha384CtxBuilder {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl KdfBuilder for HkdfSha384CtxBuilder {
+    fn for_derivation(&mut self) -> Box<dyn Kdf> {
+        Box::new(HkdfCtx {
+            ctx: new_hkdf_ctx(b"SHA384\0"),
+        })
+    }
+}
+
+pub struct HkdfCtx {
+    ctx: *mut evp::EVP_KDF_CTX,
+}
+
+impl Kdf for HkdfCtx {
+    fn extract(&mut self, salt: &[u8], ikm: &[u8], prk: &mut [u8]) {
+        unsafe {
+            let params = [
+                evp::OSSL_PARAM_construct_utf8_string(
+                    b"mode\0".as_ptr() as _,
+                    b"EXTRACT_ONLY\0".as_ptr() as *mut _,
+                    0,
+                ),
+                evp::OSSL_PARAM_construct_octet_string(
+                    b"salt\0".as_ptr() as _,
+                    salt.as_ptr() as *mut _,
+                    salt.len() as _,
+                ),
+                evp::OSSL_PARAM_construct_octet_string(
+                    b"key\0".as_ptr() as _,
+                    ikm.as_ptr() as *mut _,
+                    ikm.len() as _,
+                ),
+                evp::OSSL_PARAM_construct_end(),
+            ];
+            evp::EVP_KDF_derive(self.ctx, prk.as_mut_ptr(), prk.len() as _, params.as_ptr());
+        }
+    }
+
+    fn expand(&mut self, prk: &[u8], info: &[u8], okm: &mut [u8]) {
+        unsafe {
+            let params = [
+                evp::OSSL_PARAM_construct_utf8_string(
+                    b"mode\0".as_ptr() as _,
+                    b"EXPAND_ONLY\0".as_ptr() as *mut _,
+                    0,
+                ),
+                evp::OSSL_PARAM_construct_octet_string(
+                    b"key\0".as_ptr() as _,
+                    prk.as_ptr() as *mut _,
+                    prk.len() as _,
+                ),
+                evp::OSSL_PARAM_construct_octet_string(
+                    b"info\0".as_ptr() as _,
+                    info.as_ptr() as *mut _,
+                    info.len() as _,
+                ),
+                evp::OSSL_PARAM_construct_end(),
+            ];
+            evp::EVP_KDF_derive(self.ctx, okm.as_mut_ptr(), okm.len() as _, params.as_ptr());
+        }
+    }
+}
+
+impl Drop for HkdfCtx {
+    fn drop(&mut self) {
+        unsafe {
+            evp::EVP_KDF_CTX_free(self.ctx);
+        }
+    }
+}
+
+/// The SP 800-108 KDF in counter mode with HMAC-SHA256. The fixed input
+/// is passed as the label, without OpenSSL's separator and length, which
+/// the caller has already encoded.
+pub struct KbkdfCounterHmacSha256CtxBuilder;
+
+impl KbkdfCounterHmacSha256CtxBuilder {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl KbkdfBuilder for KbkdfCounterHmacSha256CtxBuilder {
+    fn for_derivation(&mut self) -> Box<dyn Kbkdf> {
+        let mut no: c_int = 0;
+        let ctx = unsafe {
+            let params = [
+                evp::OSSL_PARAM_construct_utf8_string(
+                    b"mode\0".as_ptr() as _,
+                    b"counter\0".as_ptr() as *mut _,
+                    0,
+                ),
+                evp::OSSL_PARAM_construct_utf8_string(
+                    b"mac\0".as_ptr() as _,
+                    b"HMAC\0".as_ptr() as *mut _,
+                    0,
+                ),
+                evp::OSSL_PARAM_construct_utf8_string(
+                    b"digest\0".as_ptr() as _,
+                    b"SHA256\0".as_ptr() as *mut _,
+                    0,
+                ),
+                evp::OSSL_PARAM_construct_int(b"use-l\0".as_ptr() as _, &mut no),
+                evp::OSSL_PARAM_construct_int(b"use-separator\0".as_ptr() as _, &mut no),
+                evp::OSSL_PARAM_construct_end(),
+            ];
+            new_kdf_ctx(b"KBKDF\0", &params)
+        };
+        Box::new(KbkdfCtx { ctx })
+    }
+}
+
+pub struct KbkdfCtx {
+    ctx: *mut evp::EVP_KDF_CTX,
+}
+
+impl Kbkdf for KbkdfCtx {
+    fn derive(&mut self, key: &[u8], fixed_input: &[u8], okm: &mut [u8]) {
+        unsafe {
+            let params = [
+                evp::OSSL_PARAM_construct_octet_string(
+                    b"key\0".as_ptr() as _,
+                    key.as_ptr() as *mut _,
+                    key.len() as _,
+                ),
+                evp::OSSL_PARAM_construct_octet_string(
+                    b"salt\0".as_ptr() as _,
+                    fixed_input.as_ptr() as *mut _,
+                    fixed_input.len() as _,
+                ),
+                evp::OSSL_PARAM_construct_end(),
+            ];
+            evp::EVP_KDF_derive(self.ctx, okm.as_mut_ptr(), okm.len() as _, params.as_ptr());
+        }
+    }
+}
+
+impl Drop for KbkdfCtx {
+    fn drop(&mut self) {
+        unsafe {
+            evp::EVP_KDF_CTX_free(self.ctx);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cipher_bench::{check_kbkdf_sp800_108, check_tls13_rfc8448};
+
+    #[test]
+    fn tls13_rfc8448() {
+        check_tls13_rfc8448(HkdfSha256CtxBuilder::new());
+    }
+
+    #[test]
+    fn kbkdf_sp800_108() {
+        check_kbkdf_sp800_108(KbkdfCounterHmacSha256CtxBuilder::new());
+    }
+}
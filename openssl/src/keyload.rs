@@ -0,0 +1,224 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::evp;
+use cipher_bench::{
+    KeyAlgorithm, KeyDecoder, KeyDecoderBuilder, KeyEncoding, KeyFormat, KEY_PASSPHRASE,
+};
+use std::ffi::CString;
+use std::ptr;
+
+/// Decodes with OSSL_DECODER, creating a decoder context for each key as
+/// PEM_read_bio_PrivateKey and friends do internally. The input type,
+/// structure and key type are given, which narrows down the decoders
+/// OpenSSL has to try.
+pub struct OsslDecoderCtxBuilder;
+
+impl OsslDecoderCtxBuilder {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl KeyDecoderBuilder for OsslDecoderCtxBuilder {
+    fn for_decoding(
+        &mut self,
+        algorithm: &KeyAlgorithm,
+        format: &KeyFormat,
+        encoding: &KeyEncoding,
+    ) -> Box<dyn KeyDecoder> {
+        let input_type: &[u8] = match encoding {
+            KeyEncoding::Der => b"DER\0",
+            KeyEncoding::Pem => b"PEM\0",
+        };
+        let structure: &[u8] = match format {
+            KeyFormat::TypeSpecific => b"type-specific\0",
+            KeyFormat::Pkcs8 => b"PrivateKeyInfo\0",
+            KeyFormat::EncryptedPkcs8 => b"EncryptedPrivateKeyInfo\0",
+            KeyFormat::SubjectPublicKeyInfo => b"SubjectPublicKeyInfo\0",
+        };
+        let key_type: &[u8] = match algorithm {
+            KeyAlgorithm::Rsa2048 => b"RSA\0",
+            KeyAlgorithm::EcdsaP256 => b"EC\0",
+            KeyAlgorithm::Ed25519 => b"ED25519\0",
+        };
+        let selection = if format.is_private() {
+            evp::EVP_PKEY_KEYPAIR
+        } else {
+            evp::EVP_PKEY_PUBLIC_KEY
+        };
+        Box::new(OsslDecoderCtx {
+            input_type,
+            structure,
+            key_type,
+            selection: selection as _,
+        })
+    }
+}
+
+pub struct OsslDecoderCtx {
+    input_type: &'static [u8],
+    structure: &'static [u8],
+    key_type: &'static [u8],
+    selection: i32,
+}
+
+impl KeyDecoder for OsslDecoderCtx {
+    fn decode(&mut self, encoded: &[u8]) -> bool {
+        unsafe {
+            let mut pkey = ptr::null_mut();
+            let ctx = evp::OSSL_DECODER_CTX_new_for_pkey(
+                &mut pkey,
+                self.input_type.as_ptr() as _,
+                self.structure.as_ptr() as _,
+                self.key_type.as_ptr() as _,
+                self.selection,
+                ptr::null_mut(),
+                ptr::null(),
+            );
+            let _ = evp::OSSL_DECODER_CTX_set_passphrase(
+                ctx,
+                KEY_PASSPHRASE.as_ptr(),
+                KEY_PASSPHRASE.len() as _,
+            );
+
+            let mut data = encoded.as_ptr();
+            let mut len = encoded.len() as _;
+            let ret = evp::OSSL_DECODER_from_data(ctx, &mut data, &mut len);
+            evp::OSSL_DECODER_CTX_free(ctx);
+
+            let decoded = ret == 1 && !pkey.is_null();
+            evp::EVP_PKEY_free(pkey);
+            decoded
+        }
+    }
+}
+
+/// Decodes with the d2i_* and PEM_read_bio_* functions, which detect the
+/// structure themselves.
+pub struct LegacyDecoderCtxBuilder;
+
+impl LegacyDecoderCtxBuilder {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl KeyDecoderBuilder for LegacyDecoderCtxBuilder {
+    fn for_decoding(
+        &mut self,
+        algorithm: &KeyAlgorithm,
+        format: &KeyFormat,
+        encoding: &KeyEncoding,
+    ) -> Box<dyn KeyDecoder> {
+        let key_type = match algorithm {
+            KeyAlgorithm::Rsa2048 => evp::EVP_PKEY_RSA,
+            KeyAlgorithm::EcdsaP256 => evp::EVP_PKEY_EC,
+            KeyAlgorithm::Ed25519 => evp::EVP_PKEY_ED25519,
+        };
+        Box::new(LegacyDecoderCtx {
+            key_type: key_type as _,
+            format: *format,
+            encoding: *encoding,
+            passphrase: CString::new(KEY_PASSPHRASE).unwrap(),
+        })
+    }
+}
+
+pub struct LegacyDecoderCtx {
+    key_type: i32,
+    format: KeyFormat,
+    encoding: KeyEncoding,
+    passphrase: CString,
+}
+
+impl KeyDecoder for LegacyDecoderCtx {
+    fn decode(&mut self, encoded: &[u8]) -> bool {
+        unsafe {
+            let mut data = encoded.as_ptr();
+            let pkey = match (&self.encoding, &self.format) {
+                (KeyEncoding::Der, KeyFormat::TypeSpecific)
+                | (KeyEncoding::Der, KeyFormat::Pkcs8) => evp::d2i_PrivateKey(
+                    self.key_type,
+                    ptr::null_mut(),
+                    &mut data,
+                    encoded.len() as _,
+                ),
+                (KeyEncoding::Der, KeyFormat::SubjectPublicKeyInfo) => {
+                    evp::d2i_PUBKEY(ptr::null_mut(), &mut data, encoded.len() as _)
+                }
+                (KeyEncoding::Der, KeyFormat::EncryptedPkcs8) => {
+                    let bio = evp::BIO_new_mem_buf(data as _, encoded.len() as _);
+                    let pkey = evp::d2i_PKCS8PrivateKey_bio(
+                        bio,
+                        ptr::null_mut(),
+                        None,
+                        self.passphrase.as_ptr() as _,
+                    );
+                    evp::BIO_free(bio);
+                    pkey
+                }
+                (KeyEncoding::Pem, KeyFormat::SubjectPublicKeyInfo) => {
+                    let bio = evp::BIO_new_mem_buf(data as _, encoded.len() as _);
+                    let pkey =
+                        evp::PEM_read_bio_PUBKEY(bio, ptr::null_mut(), None, ptr::null_mut());
+                    evp::BIO_free(bio);
+                    pkey
+                }
+                (KeyEncoding::Pem, _) => {
+                    // Without a callback, the last argument is taken as
+                    // the passphrase.
+                    let bio = evp::BIO_new_mem_buf(data as _, encoded.len() as _);
+                    let pkey = evp::PEM_read_bio_PrivateKey(
+                        bio,
+                        ptr::null_mut(),
+                        None,
+                        self.passphrase.as_ptr() as _,
+                    );
+                    evp::BIO_free(bio);
+                    pkey
+                }
+            };
+
+            let decoded = !pkey.is_null();
+            evp::EVP_PKEY_free(pkey);
+            decoded
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cipher_bench::key_fixture;
+
+    #[test]
+    fn decode_fixtures() {
+        for algorithm in [
+            KeyAlgorithm::Rsa2048,
+            KeyAlgorithm::EcdsaP256,
+            KeyAlgorithm::Ed25519,
+        ] {
+            for format in [
+                KeyFormat::TypeSpecific,
+                KeyFormat::Pkcs8,
+                KeyFormat::EncryptedPkcs8,
+                KeyFormat::SubjectPublicKeyInfo,
+            ] {
+                for encoding in [KeyEncoding::Der, KeyEncoding::Pem] {
+                    let encoded = match key_fixture(&algorithm, &format, &encoding) {
+                        Some(encoded) => encoded,
+                        None => continue,
+                    };
+
+                    let mut builder = OsslDecoderCtxBuilder::new();
+                    let mut ctx = builder.for_decoding(&algorithm, &format, &encoding);
+                    assert!(ctx.decode(encoded));
+
+                    let mut builder = LegacyDecoderCtxBuilder::new();
+                    let mut ctx = builder.for_decoding(&algorithm, &format, &encoding);
+                    assert!(ctx.decode(encoded));
+                }
+            }
+        }
+    }
+}
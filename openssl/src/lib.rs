@@ -7,3 +7,32 @@ pub use block::*;
 
 mod aead;
 pub use aead::*;
+
+mod mac;
+pub use mac::*;
+
+mod uhash;
+pub use uhash::*;
+
+mod kdf;
+pub use kdf::*;
+
+mod password;
+pub use password::*;
+
+mod pubkey;
+pub use pubkey::*;
+
+#[cfg(have_hpke)]
+mod hpke;
+#[cfg(have_hpke)]
+pub use hpke::*;
+
+mod bignum;
+pub use bignum::*;
+
+mod rng;
+pub use rng::*;
+
+mod keyload;
+pub use keyload::*;
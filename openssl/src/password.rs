@@ -0,0 +1,252 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::evp;
+use cipher_bench::{PasswordHash, PasswordHashBuilder, PasswordHashCost};
+use std::ptr;
+
+pub struct Pbkdf2Sha256CtxBuilder {
+    cost: Option<PasswordHashCost>,
+}
+
+impl Pbkdf2Sha256CtxBuilder {
+    pub fn new() -> Self {
+        Self { cost: None }
+    }
+}
+
+impl PasswordHashBuilder for Pbkdf2Sha256CtxBuilder {
+    fn cost(&mut self, cost: &PasswordHashCost) -> &mut Self {
+        self.cost.replace(*cost);
+        self
+    }
+
+    fn for_derivation(&mut self) -> Box<dyn PasswordHash> {
+        Box::new(Pbkdf2Ctx {
+            md: unsafe { evp::EVP_sha256() },
+            iterations: self.cost.take().unwrap().time,
+        })
+    }
+}
+
+pub struct Pbkdf2Sha512CtxBuilder {
+    cost: Option<PasswordHashCost>,
+}
+
+impl Pbkdf2Sha512CtxBuilder {
+    pub fn new() -> Self {
+        Self { cost: None }
+    }
+}
+
+impl PasswordHashBuilder for Pbkdf2Sha512CtxBuilder {
+    fn cost(&mut self, cost: &PasswordHashCost) -> &mut Self {
+        self.cost.replace(*cost);
+        self
+    }
+
+    fn for_derivation(&mut self) -> Box<dyn PasswordHash> {
+        Box::new(Pbkdf2Ctx {
+            md: unsafe { evp::EVP_sha512() },
+            iterations: self.cost.take().unwrap().time,
+        })
+    }
+}
+
+pub struct Pbkdf2Ctx {
+    md: *const evp::EVP_MD,
+    iterations: u32,
+}
+
+impl PasswordHash for Pbkdf2Ctx {
+    fn derive(&mut self, password: &[u8], salt: &[u8], key: &mut [u8]) {
+        unsafe {
+            evp::PKCS5_PBKDF2_HMAC(
+                password.as_ptr() as _,
+                password.len() as _,
+                salt.as_ptr() as _,
+                salt.len() as _,
+                self.iterations as _,
+                self.md,
+                key.len() as _,
+                key.as_mut_ptr() as _,
+            );
+        }
+    }
+}
+
+pub struct ScryptCtxBuilder {
+    cost: Option<PasswordHashCost>,
+}
+
+impl ScryptCtxBuilder {
+    pub fn new() -> Self {
+        Self { cost: None }
+    }
+}
+
+impl PasswordHashBuilder for ScryptCtxBuilder {
+    fn cost(&mut self, cost: &PasswordHashCost) -> &mut Self {
+        self.cost.replace(*cost);
+        self
+    }
+
+    fn for_derivation(&mut self) -> Box<dyn PasswordHash> {
+        let cost = self.cost.take().unwrap();
+        Box::new(ScryptCtx {
+            n: cost.memory as _,
+            r: 8,
+            p: cost.parallelism as _,
+        })
+    }
+}
+
+pub struct ScryptCtx {
+    n: u64,
+    r: u64,
+    p: u64,
+}
+
+impl PasswordHash for ScryptCtx {
+    fn derive(&mut self, password: &[u8], salt: &[u8], key: &mut [u8]) {
+        unsafe {
+            // Lift the default 32 MiB memory limit, which would
+            // otherwise reject N >= 2^15 with r = 8.
+            evp::EVP_PBE_scrypt(
+                password.as_ptr() as _,
+                password.len() as _,
+                salt.as_ptr() as _,
+                salt.len() as _,
+                self.n,
+                self.r,
+                self.p,
+                u64::MAX,
+                key.as_mut_ptr() as _,
+                key.len() as _,
+            );
+        }
+    }
+}
+
+const ARGON2ID: &[u8] = b"ARGON2ID\0";
+
+pub struct Argon2idCtxBuilder {
+    cost: Option<PasswordHashCost>,
+}
+
+impl Argon2idCtxBuilder {
+    pub fn new() -> Self {
+        Self { cost: None }
+    }
+
+    /// Argon2 is only provided by OpenSSL 3.2 or later.
+    pub fn is_supported() -> bool {
+        unsafe {
+            let kdf = evp::EVP_KDF_fetch(
+                ptr::null_mut::<evp::OSSL_LIB_CTX>(),
+                ARGON2ID.as_ptr() as _,
+                ptr::null(),
+            );
+            evp::EVP_KDF_free(kdf);
+            !kdf.is_null()
+        }
+    }
+}
+
+impl PasswordHashBuilder for Argon2idCtxBuilder {
+    fn cost(&mut self, cost: &PasswordHashCost) -> &mut Self {
+        self.cost.replace(*cost);
+        self
+    }
+
+    fn for_derivation(&mut self) -> Box<dyn PasswordHash> {
+        let ctx = unsafe {
+            let kdf = evp::EVP_KDF_fetch(
+                ptr::null_mut::<evp::OSSL_LIB_CTX>(),
+                ARGON2ID.as_ptr() as _,
+                ptr::null(),
+            );
+            let ctx = evp::EVP_KDF_CTX_new(kdf);
+            evp::EVP_KDF_free(kdf);
+            ctx
+        };
+        Box::new(Argon2idCtx {
+            ctx,
+            cost: self.cost.take().unwrap(),
+        })
+    }
+}
+
+pub struct Argon2idCtx {
+    ctx: *mut evp::EVP_KDF_CTX,
+    cost: PasswordHashCost,
+}
+
+impl PasswordHash for Argon2idCtx {
+    fn derive(&mut self, password: &[u8], salt: &[u8], key: &mut [u8]) {
+        // Lanes are computed sequentially; multi-threading would
+        // require raising the library-wide limit with
+        // OSSL_set_max_threads.
+        let mut threads: u32 = 1;
+        unsafe {
+            let params = [
+                evp::OSSL_PARAM_construct_octet_string(
+                    b"pass\0".as_ptr() as _,
+                    password.as_ptr() as *mut _,
+                    password.len() as _,
+                ),
+                evp::OSSL_PARAM_construct_octet_string(
+                    b"salt\0".as_ptr() as _,
+                    salt.as_ptr() as *mut _,
+                    salt.len() as _,
+                ),
+                evp::OSSL_PARAM_construct_uint32(b"iter\0".as_ptr() as _, &mut self.cost.time),
+                evp::OSSL_PARAM_construct_uint32(b"memcost\0".as_ptr() as _, &mut self.cost.memory),
+                evp::OSSL_PARAM_construct_uint32(
+                    b"lanes\0".as_ptr() as _,
+                    &mut self.cost.parallelism,
+                ),
+                evp::OSSL_PARAM_construct_uint32(b"threads\0".as_ptr() as _, &mut threads),
+                evp::OSSL_PARAM_construct_end(),
+            ];
+            evp::EVP_KDF_derive(self.ctx, key.as_mut_ptr(), key.len() as _, params.as_ptr());
+        }
+    }
+}
+
+impl Drop for Argon2idCtx {
+    fn drop(&mut self) {
+        unsafe {
+            evp::EVP_KDF_CTX_free(self.ctx);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // RFC 7914, section 12.
+    #[test]
+    fn scrypt_rfc7914() {
+        let expected = [
+            0xfd, 0xba, 0xbe, 0x1c, 0x9d, 0x34, 0x72, 0x00, 0x78, 0x56, 0xe7, 0x19, 0x0d, 0x01,
+            0xe9, 0xfe, 0x7c, 0x6a, 0xd7, 0xcb, 0xc8, 0x23, 0x78, 0x30, 0xe7, 0x73, 0x76, 0x63,
+            0x4b, 0x37, 0x31, 0x62,
+        ];
+
+        let mut key = vec![0u8; 32];
+
+        let mut builder = ScryptCtxBuilder::new();
+
+        let mut ctx = builder
+            .cost(&PasswordHashCost {
+                time: 0,
+                memory: 1024,
+                parallelism: 16,
+            })
+            .for_derivation();
+        ctx.derive(b"password", b"NaCl", &mut key);
+
+        assert_eq!(key, expected);
+    }
+}
@@ -0,0 +1,906 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::evp;
+use cipher_bench::{AsymmetricCipher, AsymmetricCipherBuilder, Signature, SignatureBuilder};
+use cipher_bench::{Kem, KemBuilder, KeyAgreement, KeyAgreementBuilder};
+use cipher_bench::{ScalarMul, ScalarMulBuilder};
+use std::os::raw::c_uint;
+use std::ptr;
+
+fn generate_rsa(bits: u32) -> *mut evp::EVP_PKEY {
+    unsafe {
+        let ctx = evp::EVP_PKEY_CTX_new_from_name(
+            ptr::null_mut::<evp::OSSL_LIB_CTX>(),
+            b"RSA\0".as_ptr() as _,
+            ptr::null(),
+        );
+        let mut pkey = ptr::null_mut::<evp::EVP_PKEY>();
+        let _ = evp::EVP_PKEY_keygen_init(ctx);
+        let _ = evp::EVP_PKEY_CTX_set_rsa_keygen_bits(ctx, bits as _);
+        let _ = evp::EVP_PKEY_generate(ctx, &mut pkey);
+        evp::EVP_PKEY_CTX_free(ctx);
+        pkey
+    }
+}
+
+/// Generates a key in a named group, such as an EC curve or an FFDHE group.
+fn generate_group(name: &[u8], group: &[u8]) -> *mut evp::EVP_PKEY {
+    unsafe {
+        let ctx = evp::EVP_PKEY_CTX_new_from_name(
+            ptr::null_mut::<evp::OSSL_LIB_CTX>(),
+            name.as_ptr() as _,
+            ptr::null(),
+        );
+        let mut pkey = ptr::null_mut::<evp::EVP_PKEY>();
+        let _ = evp::EVP_PKEY_keygen_init(ctx);
+        let _ = evp::EVP_PKEY_CTX_set_group_name(ctx, group.as_ptr() as _);
+        let _ = evp::EVP_PKEY_generate(ctx, &mut pkey);
+        evp::EVP_PKEY_CTX_free(ctx);
+        pkey
+    }
+}
+
+pub struct RsaPkcs1Sha256CtxBuilder {
+    bits: u32,
+}
+
+impl RsaPkcs1Sha256CtxBuilder {
+    pub fn new(bits: u32) -> Self {
+        Self { bits }
+    }
+}
+
+impl SignatureBuilder for RsaPkcs1Sha256CtxBuilder {
+    fn generate(&mut self) -> Box<dyn Signature> {
+        let pkey = generate_rsa(self.bits);
+        Box::new(EvpPkeySignatureCtx::new(
+            pkey,
+            unsafe { evp::EVP_sha256() },
+            |ctx| unsafe {
+                let _ = evp::EVP_PKEY_CTX_set_rsa_padding(ctx, evp::RSA_PKCS1_PADDING as _);
+            },
+        ))
+    }
+}
+
+pub struct RsaPssSha256CtxBuilder {
+    bits: u32,
+}
+
+impl RsaPssSha256CtxBuilder {
+    pub fn new(bits: u32) -> Self {
+        Self { bits }
+    }
+}
+
+impl SignatureBuilder for RsaPssSha256CtxBuilder {
+    fn generate(&mut self) -> Box<dyn Signature> {
+        let pkey = generate_rsa(self.bits);
+        Box::new(EvpPkeySignatureCtx::new(
+            pkey,
+            unsafe { evp::EVP_sha256() },
+            |ctx| unsafe {
+                let _ = evp::EVP_PKEY_CTX_set_rsa_padding(ctx, evp::RSA_PKCS1_PSS_PADDING as _);
+                let _ = evp::EVP_PKEY_CTX_set_rsa_pss_saltlen(ctx, evp::RSA_PSS_SALTLEN_DIGEST);
+            },
+        ))
+    }
+}
+
+macro_rules! ecdsa {
+    ($builder:ident, $group:expr, $md:ident) => {
+        pub struct $builder;
+
+        impl $builder {
+            pub fn new() -> Self {
+                Self
+            }
+        }
+
+        impl SignatureBuilder for $builder {
+            fn generate(&mut self) -> Box<dyn Signature> {
+                let pkey = generate_group(b"EC\0", $group);
+                Box::new(EvpPkeySignatureCtx::new(
+                    pkey,
+                    unsafe { evp::$md() },
+                    |_| {},
+                ))
+            }
+        }
+    };
+}
+
+ecdsa!(EcdsaP256Sha256CtxBuilder, b"P-256\0", EVP_sha256);
+ecdsa!(EcdsaP384Sha384CtxBuilder, b"P-384\0", EVP_sha384);
+ecdsa!(EcdsaP521Sha512CtxBuilder, b"P-521\0", EVP_sha512);
+
+/// Hash-then-sign with EVP_PKEY_sign and EVP_PKEY_verify, where the
+/// message is hashed with EVP_Digest first.
+pub struct EvpPkeySignatureCtx {
+    pkey: *mut evp::EVP_PKEY,
+    md: *const evp::EVP_MD,
+    sign: *mut evp::EVP_PKEY_CTX,
+    verify: *mut evp::EVP_PKEY_CTX,
+}
+
+impl EvpPkeySignatureCtx {
+    fn new<F>(pkey: *mut evp::EVP_PKEY, md: *const evp::EVP_MD, configure: F) -> Self
+    where
+        F: Fn(*mut evp::EVP_PKEY_CTX),
+    {
+        unsafe {
+            let sign = evp::EVP_PKEY_CTX_new_from_pkey(
+                ptr::null_mut::<evp::OSSL_LIB_CTX>(),
+                pkey,
+                ptr::null(),
+            );
+            let _ = evp::EVP_PKEY_sign_init(sign);
+            configure(sign);
+            let _ = evp::EVP_PKEY_CTX_set_signature_md(sign, md);
+
+            let verify = evp::EVP_PKEY_CTX_new_from_pkey(
+                ptr::null_mut::<evp::OSSL_LIB_CTX>(),
+                pkey,
+                ptr::null(),
+            );
+            let _ = evp::EVP_PKEY_verify_init(verify);
+            configure(verify);
+            let _ = evp::EVP_PKEY_CTX_set_signature_md(verify, md);
+
+            Self {
+                pkey,
+                md,
+                sign,
+                verify,
+            }
+        }
+    }
+
+    fn digest(&self, msg: &[u8]) -> Vec<u8> {
+        let mut digest = vec![0u8; evp::EVP_MAX_MD_SIZE as usize];
+        let mut len: c_uint = 0;
+        unsafe {
+            evp::EVP_Digest(
+                msg.as_ptr() as _,
+                msg.len() as _,
+                digest.as_mut_ptr(),
+                &mut len,
+                self.md,
+                ptr::null_mut::<evp::ENGINE>(),
+            );
+        }
+        digest.truncate(len as usize);
+        digest
+    }
+}
+
+impl Signature for EvpPkeySignatureCtx {
+    fn sign(&mut self, msg: &[u8]) -> Vec<u8> {
+        let digest = self.digest(msg);
+        let mut siglen = 0;
+        unsafe {
+            evp::EVP_PKEY_sign(
+                self.sign,
+                ptr::null_mut(),
+                &mut siglen,
+                digest.as_ptr(),
+                digest.len() as _,
+            );
+            let mut sig = vec![0u8; siglen as usize];
+            evp::EVP_PKEY_sign(
+                self.sign,
+                sig.as_mut_ptr(),
+                &mut siglen,
+                digest.as_ptr(),
+                digest.len() as _,
+            );
+            sig.truncate(siglen as usize);
+            sig
+        }
+    }
+
+    fn verify(&mut self, msg: &[u8], sig: &[u8]) -> bool {
+        let digest = self.digest(msg);
+        unsafe {
+            evp::EVP_PKEY_verify(
+                self.verify,
+                sig.as_ptr(),
+                sig.len() as _,
+                digest.as_ptr(),
+                digest.len() as _,
+            ) == 1
+        }
+    }
+}
+
+impl Drop for EvpPkeySignatureCtx {
+    fn drop(&mut self) {
+        unsafe {
+            evp::EVP_PKEY_CTX_free(self.sign);
+            evp::EVP_PKEY_CTX_free(self.verify);
+            evp::EVP_PKEY_free(self.pkey);
+        }
+    }
+}
+
+fn generate_key(name: &[u8]) -> *mut evp::EVP_PKEY {
+    unsafe {
+        let ctx = evp::EVP_PKEY_CTX_new_from_name(
+            ptr::null_mut::<evp::OSSL_LIB_CTX>(),
+            name.as_ptr() as _,
+            ptr::null(),
+        );
+        let mut pkey = ptr::null_mut::<evp::EVP_PKEY>();
+        let _ = evp::EVP_PKEY_keygen_init(ctx);
+        let _ = evp::EVP_PKEY_generate(ctx, &mut pkey);
+        evp::EVP_PKEY_CTX_free(ctx);
+        pkey
+    }
+}
+
+pub struct Ed25519CtxBuilder;
+
+impl Ed25519CtxBuilder {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl SignatureBuilder for Ed25519CtxBuilder {
+    fn generate(&mut self) -> Box<dyn Signature> {
+        Box::new(EvpDigestSignatureCtx::new(generate_key(b"ED25519\0")))
+    }
+}
+
+pub struct Ed448CtxBuilder;
+
+impl Ed448CtxBuilder {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl SignatureBuilder for Ed448CtxBuilder {
+    fn generate(&mut self) -> Box<dyn Signature> {
+        Box::new(EvpDigestSignatureCtx::new(generate_key(b"ED448\0")))
+    }
+}
+
+/// Returns whether the default provider implements the key type, as the
+/// post-quantum algorithms need OpenSSL 3.5 or later.
+fn is_key_type_supported(name: &[u8]) -> bool {
+    unsafe {
+        let keymgmt = evp::EVP_KEYMGMT_fetch(
+            ptr::null_mut::<evp::OSSL_LIB_CTX>(),
+            name.as_ptr() as _,
+            ptr::null(),
+        );
+        evp::EVP_KEYMGMT_free(keymgmt);
+        !keymgmt.is_null()
+    }
+}
+
+macro_rules! ml_dsa {
+    ($builder:ident, $name:expr) => {
+        pub struct $builder;
+
+        impl $builder {
+            pub fn new() -> Self {
+                Self
+            }
+
+            pub fn is_supported() -> bool {
+                is_key_type_supported($name)
+            }
+        }
+
+        impl SignatureBuilder for $builder {
+            fn generate(&mut self) -> Box<dyn Signature> {
+                Box::new(EvpDigestSignatureCtx::new(generate_key($name)))
+            }
+        }
+    };
+}
+
+ml_dsa!(MlDsa44CtxBuilder, b"ML-DSA-44\0");
+ml_dsa!(MlDsa65CtxBuilder, b"ML-DSA-65\0");
+ml_dsa!(MlDsa87CtxBuilder, b"ML-DSA-87\0");
+
+/// One-shot signing with EVP_DigestSign and EVP_DigestVerify, for
+/// algorithms such as EdDSA and ML-DSA that hash the message themselves.
+///
+/// Some providers refuse a second one-shot operation on the same context,
+/// so each operation starts with a fresh init.
+pub struct EvpDigestSignatureCtx {
+    pkey: *mut evp::EVP_PKEY,
+    sign: *mut evp::EVP_MD_CTX,
+    verify: *mut evp::EVP_MD_CTX,
+}
+
+impl EvpDigestSignatureCtx {
+    fn new(pkey: *mut evp::EVP_PKEY) -> Self {
+        unsafe {
+            Self {
+                pkey,
+                sign: evp::EVP_MD_CTX_new(),
+                verify: evp::EVP_MD_CTX_new(),
+            }
+        }
+    }
+}
+
+impl Signature for EvpDigestSignatureCtx {
+    fn sign(&mut self, msg: &[u8]) -> Vec<u8> {
+        let mut siglen = 0;
+        unsafe {
+            let _ = evp::EVP_DigestSignInit_ex(
+                self.sign,
+                ptr::null_mut(),
+                ptr::null(),
+                ptr::null_mut::<evp::OSSL_LIB_CTX>(),
+                ptr::null(),
+                self.pkey,
+                ptr::null(),
+            );
+            evp::EVP_DigestSign(
+                self.sign,
+                ptr::null_mut(),
+                &mut siglen,
+                msg.as_ptr(),
+                msg.len() as _,
+            );
+            let mut sig = vec![0u8; siglen as usize];
+            evp::EVP_DigestSign(
+                self.sign,
+                sig.as_mut_ptr(),
+                &mut siglen,
+                msg.as_ptr(),
+                msg.len() as _,
+            );
+            sig.truncate(siglen as usize);
+            sig
+        }
+    }
+
+    fn verify(&mut self, msg: &[u8], sig: &[u8]) -> bool {
+        unsafe {
+            let _ = evp::EVP_DigestVerifyInit_ex(
+                self.verify,
+                ptr::null_mut(),
+                ptr::null(),
+                ptr::null_mut::<evp::OSSL_LIB_CTX>(),
+                ptr::null(),
+                self.pkey,
+                ptr::null(),
+            );
+            evp::EVP_DigestVerify(
+                self.verify,
+                sig.as_ptr(),
+                sig.len() as _,
+                msg.as_ptr(),
+                msg.len() as _,
+            ) == 1
+        }
+    }
+}
+
+impl Drop for EvpDigestSignatureCtx {
+    fn drop(&mut self) {
+        unsafe {
+            evp::EVP_MD_CTX_free(self.sign);
+            evp::EVP_MD_CTX_free(self.verify);
+            evp::EVP_PKEY_free(self.pkey);
+        }
+    }
+}
+
+macro_rules! key_agreement {
+    ($builder:ident, $generate:expr) => {
+        pub struct $builder;
+
+        impl $builder {
+            pub fn new() -> Self {
+                Self
+            }
+        }
+
+        impl KeyAgreementBuilder for $builder {
+            fn generate(&mut self) -> Box<dyn KeyAgreement> {
+                Box::new(EvpPkeyKeyAgreementCtx::new($generate))
+            }
+        }
+    };
+}
+
+key_agreement!(X25519CtxBuilder, generate_key(b"X25519\0"));
+key_agreement!(X448CtxBuilder, generate_key(b"X448\0"));
+key_agreement!(EcdhP256CtxBuilder, generate_group(b"EC\0", b"P-256\0"));
+key_agreement!(EcdhP384CtxBuilder, generate_group(b"EC\0", b"P-384\0"));
+key_agreement!(Ffdhe2048CtxBuilder, generate_group(b"DH\0", b"ffdhe2048\0"));
+key_agreement!(Ffdhe3072CtxBuilder, generate_group(b"DH\0", b"ffdhe3072\0"));
+key_agreement!(Ffdhe4096CtxBuilder, generate_group(b"DH\0", b"ffdhe4096\0"));
+
+/// Key agreement with EVP_PKEY_derive, where the peer key is decoded
+/// with EVP_PKEY_set1_encoded_public_key.
+pub struct EvpPkeyKeyAgreementCtx {
+    pkey: *mut evp::EVP_PKEY,
+    derive: *mut evp::EVP_PKEY_CTX,
+}
+
+impl EvpPkeyKeyAgreementCtx {
+    fn new(pkey: *mut evp::EVP_PKEY) -> Self {
+        unsafe {
+            let derive = evp::EVP_PKEY_CTX_new_from_pkey(
+                ptr::null_mut::<evp::OSSL_LIB_CTX>(),
+                pkey,
+                ptr::null(),
+            );
+            let _ = evp::EVP_PKEY_derive_init(derive);
+            Self { pkey, derive }
+        }
+    }
+}
+
+impl KeyAgreement for EvpPkeyKeyAgreementCtx {
+    fn public_key(&self) -> Vec<u8> {
+        unsafe {
+            let mut encoded = ptr::null_mut::<u8>();
+            let len = evp::EVP_PKEY_get1_encoded_public_key(self.pkey, &mut encoded);
+            let public = std::slice::from_raw_parts(encoded, len as usize).to_vec();
+            evp::CRYPTO_free(encoded as _, ptr::null(), 0);
+            public
+        }
+    }
+
+    fn derive(&mut self, peer: &[u8]) -> Vec<u8> {
+        unsafe {
+            let peer_key = evp::EVP_PKEY_new();
+            let _ = evp::EVP_PKEY_copy_parameters(peer_key, self.pkey);
+            let _ = evp::EVP_PKEY_set1_encoded_public_key(peer_key, peer.as_ptr(), peer.len() as _);
+            let _ = evp::EVP_PKEY_derive_set_peer(self.derive, peer_key);
+
+            let mut secretlen = 0;
+            evp::EVP_PKEY_derive(self.derive, ptr::null_mut(), &mut secretlen);
+            let mut secret = vec![0u8; secretlen as usize];
+            evp::EVP_PKEY_derive(self.derive, secret.as_mut_ptr(), &mut secretlen);
+            secret.truncate(secretlen as usize);
+
+            evp::EVP_PKEY_free(peer_key);
+            secret
+        }
+    }
+}
+
+impl Drop for EvpPkeyKeyAgreementCtx {
+    fn drop(&mut self) {
+        unsafe {
+            evp::EVP_PKEY_CTX_free(self.derive);
+            evp::EVP_PKEY_free(self.pkey);
+        }
+    }
+}
+
+macro_rules! kem {
+    ($builder:ident, $name:expr) => {
+        pub struct $builder;
+
+        impl $builder {
+            pub fn new() -> Self {
+                Self
+            }
+
+            pub fn is_supported() -> bool {
+                is_key_type_supported($name)
+            }
+        }
+
+        impl KemBuilder for $builder {
+            fn generate(&mut self) -> Box<dyn Kem> {
+                Box::new(EvpPkeyKemCtx::new(generate_key($name)))
+            }
+        }
+    };
+}
+
+kem!(MlKem512CtxBuilder, b"ML-KEM-512\0");
+kem!(MlKem768CtxBuilder, b"ML-KEM-768\0");
+kem!(MlKem1024CtxBuilder, b"ML-KEM-1024\0");
+kem!(X25519MlKem768CtxBuilder, b"X25519MLKEM768\0");
+
+/// Encapsulation with EVP_PKEY_encapsulate, where the peer key is decoded
+/// with EVP_PKEY_set1_encoded_public_key.
+pub struct EvpPkeyKemCtx {
+    pkey: *mut evp::EVP_PKEY,
+    decapsulate: *mut evp::EVP_PKEY_CTX,
+}
+
+impl EvpPkeyKemCtx {
+    fn new(pkey: *mut evp::EVP_PKEY) -> Self {
+        unsafe {
+            let decapsulate = evp::EVP_PKEY_CTX_new_from_pkey(
+                ptr::null_mut::<evp::OSSL_LIB_CTX>(),
+                pkey,
+                ptr::null(),
+            );
+            let _ = evp::EVP_PKEY_decapsulate_init(decapsulate, ptr::null());
+            Self { pkey, decapsulate }
+        }
+    }
+}
+
+impl Kem for EvpPkeyKemCtx {
+    fn public_key(&self) -> Vec<u8> {
+        unsafe {
+            let mut encoded = ptr::null_mut::<u8>();
+            let len = evp::EVP_PKEY_get1_encoded_public_key(self.pkey, &mut encoded);
+            let public = std::slice::from_raw_parts(encoded, len as usize).to_vec();
+            evp::CRYPTO_free(encoded as _, ptr::null(), 0);
+            public
+        }
+    }
+
+    fn encapsulate(&mut self, peer: &[u8]) -> (Vec<u8>, Vec<u8>) {
+        unsafe {
+            let peer_key = evp::EVP_PKEY_new();
+            let _ = evp::EVP_PKEY_copy_parameters(peer_key, self.pkey);
+            let _ = evp::EVP_PKEY_set1_encoded_public_key(peer_key, peer.as_ptr(), peer.len() as _);
+            let ctx = evp::EVP_PKEY_CTX_new_from_pkey(
+                ptr::null_mut::<evp::OSSL_LIB_CTX>(),
+                peer_key,
+                ptr::null(),
+            );
+            let _ = evp::EVP_PKEY_encapsulate_init(ctx, ptr::null());
+
+            let mut ctextlen = 0;
+            let mut secretlen = 0;
+            evp::EVP_PKEY_encapsulate(
+                ctx,
+                ptr::null_mut(),
+                &mut ctextlen,
+                ptr::null_mut(),
+                &mut secretlen,
+            );
+            let mut ctext = vec![0u8; ctextlen as usize];
+            let mut secret = vec![0u8; secretlen as usize];
+            evp::EVP_PKEY_encapsulate(
+                ctx,
+                ctext.as_mut_ptr(),
+                &mut ctextlen,
+                secret.as_mut_ptr(),
+                &mut secretlen,
+            );
+            ctext.truncate(ctextlen as usize);
+            secret.truncate(secretlen as usize);
+
+            evp::EVP_PKEY_CTX_free(ctx);
+            evp::EVP_PKEY_free(peer_key);
+            (ctext, secret)
+        }
+    }
+
+    fn decapsulate(&mut self, ctext: &[u8]) -> Vec<u8> {
+        let mut secretlen = 0;
+        unsafe {
+            evp::EVP_PKEY_decapsulate(
+                self.decapsulate,
+                ptr::null_mut(),
+                &mut secretlen,
+                ctext.as_ptr(),
+                ctext.len() as _,
+            );
+            let mut secret = vec![0u8; secretlen as usize];
+            evp::EVP_PKEY_decapsulate(
+                self.decapsulate,
+                secret.as_mut_ptr(),
+                &mut secretlen,
+                ctext.as_ptr(),
+                ctext.len() as _,
+            );
+            secret.truncate(secretlen as usize);
+            secret
+        }
+    }
+}
+
+impl Drop for EvpPkeyKemCtx {
+    fn drop(&mut self) {
+        unsafe {
+            evp::EVP_PKEY_CTX_free(self.decapsulate);
+            evp::EVP_PKEY_free(self.pkey);
+        }
+    }
+}
+
+macro_rules! scalar_mul {
+    ($builder:ident, $nid:ident) => {
+        pub struct $builder;
+
+        impl $builder {
+            pub fn new() -> Self {
+                Self
+            }
+        }
+
+        impl ScalarMulBuilder for $builder {
+            fn generate(&mut self) -> Box<dyn ScalarMul> {
+                Box::new(EcPointScalarMulCtx::new(evp::$nid as _))
+            }
+        }
+    };
+}
+
+scalar_mul!(P256ScalarMulCtxBuilder, NID_X9_62_prime256v1);
+scalar_mul!(P384ScalarMulCtxBuilder, NID_secp384r1);
+
+/// Scalar multiplication with EC_POINT_mul. The fixed-base case takes the
+/// generator path, which uses precomputed tables where available.
+pub struct EcPointScalarMulCtx {
+    group: *mut evp::EC_GROUP,
+    scalar: *mut evp::BIGNUM,
+    point: *mut evp::EC_POINT,
+    result: *mut evp::EC_POINT,
+    ctx: *mut evp::BN_CTX,
+}
+
+impl EcPointScalarMulCtx {
+    fn new(nid: i32) -> Self {
+        unsafe {
+            let group = evp::EC_GROUP_new_by_curve_name(nid);
+            let ctx = evp::BN_CTX_new();
+
+            let scalar = evp::BN_new();
+            let _ = evp::BN_rand_range(scalar, evp::EC_GROUP_get0_order(group));
+
+            let other = evp::BN_new();
+            let _ = evp::BN_rand_range(other, evp::EC_GROUP_get0_order(group));
+            let point = evp::EC_POINT_new(group);
+            let _ = evp::EC_POINT_mul(group, point, other, ptr::null(), ptr::null(), ctx);
+            evp::BN_free(other);
+
+            Self {
+                group,
+                scalar,
+                point,
+                result: evp::EC_POINT_new(group),
+                ctx,
+            }
+        }
+    }
+}
+
+impl ScalarMul for EcPointScalarMulCtx {
+    fn mul_g(&mut self) {
+        unsafe {
+            evp::EC_POINT_mul(
+                self.group,
+                self.result,
+                self.scalar,
+                ptr::null(),
+                ptr::null(),
+                self.ctx,
+            );
+        }
+    }
+
+    fn mul(&mut self) {
+        unsafe {
+            evp::EC_POINT_mul(
+                self.group,
+                self.result,
+                ptr::null(),
+                self.point,
+                self.scalar,
+                self.ctx,
+            );
+        }
+    }
+}
+
+impl Drop for EcPointScalarMulCtx {
+    fn drop(&mut self) {
+        unsafe {
+            evp::EC_POINT_free(self.result);
+            evp::EC_POINT_free(self.point);
+            evp::BN_free(self.scalar);
+            evp::BN_CTX_free(self.ctx);
+            evp::EC_GROUP_free(self.group);
+        }
+    }
+}
+
+pub struct RsaOaepSha256CtxBuilder {
+    bits: u32,
+}
+
+impl RsaOaepSha256CtxBuilder {
+    pub fn new(bits: u32) -> Self {
+        Self { bits }
+    }
+}
+
+impl AsymmetricCipherBuilder for RsaOaepSha256CtxBuilder {
+    fn generate(&mut self) -> Box<dyn AsymmetricCipher> {
+        unsafe {
+            let pkey = generate_rsa(self.bits);
+
+            let configure = |ctx: *mut evp::EVP_PKEY_CTX| {
+                let _ = evp::EVP_PKEY_CTX_set_rsa_padding(ctx, evp::RSA_PKCS1_OAEP_PADDING as _);
+                let _ = evp::EVP_PKEY_CTX_set_rsa_oaep_md(ctx, evp::EVP_sha256());
+                let _ = evp::EVP_PKEY_CTX_set_rsa_mgf1_md(ctx, evp::EVP_sha256());
+            };
+
+            let encrypt = evp::EVP_PKEY_CTX_new_from_pkey(
+                ptr::null_mut::<evp::OSSL_LIB_CTX>(),
+                pkey,
+                ptr::null(),
+            );
+            let _ = evp::EVP_PKEY_encrypt_init(encrypt);
+            configure(encrypt);
+
+            let decrypt = evp::EVP_PKEY_CTX_new_from_pkey(
+                ptr::null_mut::<evp::OSSL_LIB_CTX>(),
+                pkey,
+                ptr::null(),
+            );
+            let _ = evp::EVP_PKEY_decrypt_init(decrypt);
+            configure(decrypt);
+
+            Box::new(EvpPkeyCipherCtx {
+                pkey,
+                encrypt,
+                decrypt,
+            })
+        }
+    }
+}
+
+pub struct EvpPkeyCipherCtx {
+    pkey: *mut evp::EVP_PKEY,
+    encrypt: *mut evp::EVP_PKEY_CTX,
+    decrypt: *mut evp::EVP_PKEY_CTX,
+}
+
+impl AsymmetricCipher for EvpPkeyCipherCtx {
+    fn encrypt(&mut self, ptext: &[u8]) -> Vec<u8> {
+        let mut outlen = 0;
+        unsafe {
+            evp::EVP_PKEY_encrypt(
+                self.encrypt,
+                ptr::null_mut(),
+                &mut outlen,
+                ptext.as_ptr(),
+                ptext.len() as _,
+            );
+            let mut ctext = vec![0u8; outlen as usize];
+            evp::EVP_PKEY_encrypt(
+                self.encrypt,
+                ctext.as_mut_ptr(),
+                &mut outlen,
+                ptext.as_ptr(),
+                ptext.len() as _,
+            );
+            ctext.truncate(outlen as usize);
+            ctext
+        }
+    }
+
+    fn decrypt(&mut self, ctext: &[u8]) -> Vec<u8> {
+        let mut outlen = 0;
+        unsafe {
+            evp::EVP_PKEY_decrypt(
+                self.decrypt,
+                ptr::null_mut(),
+                &mut outlen,
+                ctext.as_ptr(),
+                ctext.len() as _,
+            );
+            let mut ptext = vec![0u8; outlen as usize];
+            evp::EVP_PKEY_decrypt(
+                self.decrypt,
+                ptext.as_mut_ptr(),
+                &mut outlen,
+                ctext.as_ptr(),
+                ctext.len() as _,
+            );
+            ptext.truncate(outlen as usize);
+            ptext
+        }
+    }
+}
+
+impl Drop for EvpPkeyCipherCtx {
+    fn drop(&mut self) {
+        unsafe {
+            evp::EVP_PKEY_CTX_free(self.encrypt);
+            evp::EVP_PKEY_CTX_free(self.decrypt);
+            evp::EVP_PKEY_free(self.pkey);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::prelude::*;
+
+    #[test]
+    fn rsa_oaep_roundtrip() {
+        let mut rng = rand::thread_rng();
+
+        let mut data_bytes = vec![0u8; 32];
+        rng.fill(data_bytes.as_mut_slice());
+
+        let mut builder = RsaOaepSha256CtxBuilder::new(2048);
+
+        let mut ctx = builder.generate();
+        let ctext = ctx.encrypt(&data_bytes);
+        let ptext = ctx.decrypt(&ctext);
+
+        assert_eq!(ptext, data_bytes);
+    }
+
+    #[test]
+    fn ecdsa_sign_verify() {
+        let mut rng = rand::thread_rng();
+
+        let mut data_bytes = vec![0u8; 1024];
+        rng.fill(data_bytes.as_mut_slice());
+
+        let mut builder = EcdsaP384Sha384CtxBuilder::new();
+
+        let mut ctx = builder.generate();
+        let sig = ctx.sign(&data_bytes);
+
+        assert!(ctx.verify(&data_bytes, &sig));
+
+        data_bytes[0] ^= 1;
+        assert!(!ctx.verify(&data_bytes, &sig));
+    }
+
+    #[test]
+    fn ed448_sign_verify() {
+        let mut rng = rand::thread_rng();
+
+        let mut data_bytes = vec![0u8; 1024];
+        rng.fill(data_bytes.as_mut_slice());
+
+        let mut builder = Ed448CtxBuilder::new();
+
+        let mut ctx = builder.generate();
+        let sig = ctx.sign(&data_bytes);
+
+        assert!(ctx.verify(&data_bytes, &sig));
+
+        data_bytes[0] ^= 1;
+        assert!(!ctx.verify(&data_bytes, &sig));
+    }
+
+    #[test]
+    fn x25519_agree() {
+        let mut builder = X25519CtxBuilder::new();
+
+        let mut alice = builder.generate();
+        let mut bob = builder.generate();
+
+        assert_eq!(
+            alice.derive(&bob.public_key()),
+            bob.derive(&alice.public_key())
+        );
+    }
+
+    #[test]
+    fn ml_kem_roundtrip() {
+        if !MlKem768CtxBuilder::is_supported() {
+            return;
+        }
+
+        let mut builder = MlKem768CtxBuilder::new();
+
+        let mut alice = builder.generate();
+        let mut bob = builder.generate();
+
+        let (ctext, secret) = bob.encapsulate(&alice.public_key());
+
+        assert_eq!(alice.decapsulate(&ctext), secret);
+    }
+}
@@ -0,0 +1,153 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::evp;
+use cipher_bench::{Rng, RngBuilder};
+use std::ptr;
+
+/// The public primary DRBG, shared by the whole process.
+pub struct RandBytesCtxBuilder;
+
+impl RandBytesCtxBuilder {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl RngBuilder for RandBytesCtxBuilder {
+    fn for_generation(&mut self) -> Box<dyn Rng> {
+        Box::new(RandBytesCtx)
+    }
+}
+
+pub struct RandBytesCtx;
+
+impl Rng for RandBytesCtx {
+    fn fill(&mut self, dst: &mut [u8]) {
+        unsafe {
+            evp::RAND_bytes(dst.as_mut_ptr(), dst.len() as _);
+        }
+    }
+}
+
+/// The private DRBG, meant for long-term secrets.
+pub struct RandPrivBytesCtxBuilder;
+
+impl RandPrivBytesCtxBuilder {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl RngBuilder for RandPrivBytesCtxBuilder {
+    fn for_generation(&mut self) -> Box<dyn Rng> {
+        Box::new(RandPrivBytesCtx)
+    }
+}
+
+pub struct RandPrivBytesCtx;
+
+impl Rng for RandPrivBytesCtx {
+    fn fill(&mut self, dst: &mut [u8]) {
+        unsafe {
+            evp::RAND_priv_bytes(dst.as_mut_ptr(), dst.len() as _);
+        }
+    }
+}
+
+fn instantiate(name: &[u8], key: &[u8], value: &[u8]) -> EvpRandCtx {
+    unsafe {
+        let rand = evp::EVP_RAND_fetch(
+            ptr::null_mut::<evp::OSSL_LIB_CTX>(),
+            name.as_ptr() as _,
+            ptr::null(),
+        );
+        // Without a parent, the DRBG is seeded from the operating system.
+        let ctx = evp::EVP_RAND_CTX_new(rand, ptr::null_mut());
+        evp::EVP_RAND_free(rand);
+
+        let params = [
+            evp::OSSL_PARAM_construct_utf8_string(key.as_ptr() as _, value.as_ptr() as *mut _, 0),
+            evp::OSSL_PARAM_construct_end(),
+        ];
+        let _ = evp::EVP_RAND_instantiate(ctx, 256, 0, ptr::null(), 0, params.as_ptr());
+
+        EvpRandCtx { ctx }
+    }
+}
+
+pub struct CtrDrbgAes256CtxBuilder;
+
+impl CtrDrbgAes256CtxBuilder {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl RngBuilder for CtrDrbgAes256CtxBuilder {
+    fn for_generation(&mut self) -> Box<dyn Rng> {
+        Box::new(instantiate(b"CTR-DRBG\0", b"cipher\0", b"AES-256-CTR\0"))
+    }
+}
+
+pub struct HashDrbgSha256CtxBuilder;
+
+impl HashDrbgSha256CtxBuilder {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl RngBuilder for HashDrbgSha256CtxBuilder {
+    fn for_generation(&mut self) -> Box<dyn Rng> {
+        Box::new(instantiate(b"HASH-DRBG\0", b"digest\0", b"SHA256\0"))
+    }
+}
+
+/// An explicit DRBG instance. EVP_RAND_generate splits requests larger
+/// than the DRBG's maximum request size itself.
+pub struct EvpRandCtx {
+    ctx: *mut evp::EVP_RAND_CTX,
+}
+
+impl Rng for EvpRandCtx {
+    fn fill(&mut self, dst: &mut [u8]) {
+        unsafe {
+            let _ = evp::EVP_RAND_generate(
+                self.ctx,
+                dst.as_mut_ptr(),
+                dst.len() as _,
+                256,
+                0,
+                ptr::null(),
+                0,
+            );
+        }
+    }
+}
+
+impl Drop for EvpRandCtx {
+    fn drop(&mut self) {
+        unsafe {
+            evp::EVP_RAND_CTX_free(self.ctx);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ctr_drbg_distinct() {
+        let mut builder = CtrDrbgAes256CtxBuilder::new();
+
+        let mut ctx = builder.for_generation();
+
+        let mut first = [0u8; 32];
+        let mut second = [0u8; 32];
+        ctx.fill(&mut first);
+        ctx.fill(&mut second);
+
+        assert_ne!(first, second);
+    }
+}